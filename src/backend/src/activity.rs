@@ -0,0 +1,414 @@
+use candid::Principal;
+use ic_cdk::api::time;
+use ic_cdk::api::msg_caller;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::result::Result;
+
+use crate::types::*;
+use crate::webhook;
+
+pub const DEFAULT_MAX_ENTRIES_PER_SCOPE: u64 = 1_000;
+pub const DEFAULT_MAX_TOTAL_ENTRIES: u64 = 100_000;
+const MAX_PAGE_SIZE: u64 = 100;
+
+// Entries by id, with per-scope buckets for retention and indexes for team and user queries
+#[derive(Default)]
+pub struct ActivityLog {
+    entries: BTreeMap<ActivityId, ActivityEntry>,
+    by_scope: HashMap<String, VecDeque<ActivityId>>,
+    by_team: HashMap<TeamId, BTreeSet<ActivityId>>,
+    by_actor: HashMap<Principal, BTreeSet<ActivityId>>,
+}
+
+// Team and project an entry belongs to, used for visibility
+pub struct ActivityScope {
+    pub team_id: Option<TeamId>,
+    pub project_id: Option<ProjectId>,
+}
+
+impl ActivityScope {
+    pub fn none() -> Self {
+        ActivityScope { team_id: None, project_id: None }
+    }
+
+    pub fn team(team_id: &str) -> Self {
+        ActivityScope { team_id: Some(team_id.to_string()), project_id: None }
+    }
+
+    pub fn project(project: &Project) -> Self {
+        let team_id = match &project.owner {
+            Owner::Team(team_id) => Some(team_id.clone()),
+            Owner::User(_) => None,
+        };
+        ActivityScope { team_id, project_id: Some(project.id.clone()) }
+    }
+}
+
+// Recording
+pub fn record(entity: ActivityEntity, action: ActivityAction, scope: ActivityScope, changes: Vec<FieldChange>) {
+    // Updates that changed nothing are not worth logging or sending to webhooks
+    if action == ActivityAction::Updated && changes.is_empty() {
+        return;
+    }
+    
+    let id = crate::NEXT_ACTIVITY_ID.with(|next_id| {
+        let mut next_id = next_id.borrow_mut();
+        let id = *next_id;
+        *next_id += 1;
+        id
+    });
+    
+    let entry = ActivityEntry {
+        id,
+        actor: msg_caller(),
+        timestamp: time(),
+        entity,
+        action,
+        team_id: scope.team_id,
+        project_id: scope.project_id,
+        changes,
+    };
+    
+    webhook::enqueue(&entry);
+    
+    let retention = crate::ACTIVITY_RETENTION.with(|retention| retention.borrow().clone());
+    crate::ACTIVITY_LOG.with(|log| log.borrow_mut().push(entry, &retention));
+}
+
+pub fn change(field: &str, before: Option<String>, after: Option<String>) -> FieldChange {
+    FieldChange {
+        field: field.to_string(),
+        before,
+        after,
+    }
+}
+
+/// Push a change for `field` only if the value actually changed
+pub fn diff(changes: &mut Vec<FieldChange>, field: &str, before: Option<String>, after: Option<String>) {
+    if before != after {
+        changes.push(change(field, before, after));
+    }
+}
+
+pub fn role_value(role: &Role) -> String {
+    match role {
+        Role::Owner => "Owner",
+        Role::Manager => "Manager",
+        Role::Collaborator => "Collaborator",
+    }
+    .to_string()
+}
+
+pub fn owner_value(owner: &Owner) -> String {
+    match owner {
+        Owner::User(principal) => format!("user:{}", principal.to_text()),
+        Owner::Team(team_id) => format!("team:{}", team_id),
+    }
+}
+
+pub fn status_value(status: &ProjectStatus) -> String {
+    match status {
+        ProjectStatus::Active => "Active",
+        ProjectStatus::Archived => "Archived",
+    }
+    .to_string()
+}
+
+pub fn action_value(action: &ActivityAction) -> String {
+    match action {
+        ActivityAction::Created => "Created",
+        ActivityAction::Updated => "Updated",
+        ActivityAction::Deleted => "Deleted",
+        ActivityAction::Archived => "Archived",
+        ActivityAction::Restored => "Restored",
+        ActivityAction::OwnershipTransferred => "OwnershipTransferred",
+        ActivityAction::MemberAdded => "MemberAdded",
+        ActivityAction::MemberRemoved => "MemberRemoved",
+        ActivityAction::InviteSent => "InviteSent",
+        ActivityAction::InviteAccepted => "InviteAccepted",
+        ActivityAction::InviteDeclined => "InviteDeclined",
+        ActivityAction::InviteCancelled => "InviteCancelled",
+    }
+    .to_string()
+}
+
+pub fn event_filter_value(event_filter: &[ActivityAction]) -> String {
+    if event_filter.is_empty() {
+        return "all".to_string();
+    }
+    event_filter.iter().map(action_value).collect::<Vec<_>>().join(", ")
+}
+
+pub fn theme_value(theme: &ThemePreferences) -> String {
+    format!("{} ({})", theme.color, if theme.dark_mode { "dark" } else { "light" })
+}
+
+pub fn notification_preferences_value(preferences: &NotificationPreferences) -> String {
+    let quiet_hours = match &preferences.quiet_hours {
+        Some(quiet_hours) => format!("{}-{}", quiet_hours.start_minute, quiet_hours.end_minute),
+        None => "none".to_string(),
+    };
+    format!(
+        "{} overrides, quiet hours {}, utc offset {}",
+        preferences.deliveries.len(),
+        quiet_hours,
+        preferences.utc_offset_minutes
+    )
+}
+
+// Queries
+pub fn get_project_activity(project_id: String, offset: u64, limit: u64) -> Result<ActivityPage, Error> {
+    let caller_principal = msg_caller();
+    
+    // Only current project members can see its activity
+    let is_member = crate::PROJECTS.with(|projects| {
+        projects
+            .borrow()
+            .get(&project_id)
+            .map(|project| project.members.iter().any(|member| member.principal == caller_principal))
+    });
+    match is_member {
+        Some(true) => {}
+        Some(false) => return Err(Error::InsufficientPermissions),
+        None => return Err(Error::ProjectNotFound),
+    }
+    
+    let key = format!("project:{}", project_id);
+    Ok(crate::ACTIVITY_LOG.with(|log| {
+        let log = log.borrow();
+        log.page(log.by_scope.get(&key).into_iter().flatten().rev(), offset, limit)
+    }))
+}
+
+pub fn get_team_activity(team_id: String, offset: u64, limit: u64) -> Result<ActivityPage, Error> {
+    let caller_principal = msg_caller();
+    
+    // Only current team members can see its activity
+    let is_member = crate::TEAMS.with(|teams| {
+        teams
+            .borrow()
+            .get(&team_id)
+            .map(|team| team.members.iter().any(|member| member.principal == caller_principal))
+    });
+    match is_member {
+        Some(true) => {}
+        Some(false) => return Err(Error::InsufficientPermissions),
+        None => return Err(Error::TeamNotFound),
+    }
+    
+    Ok(crate::ACTIVITY_LOG.with(|log| {
+        let log = log.borrow();
+        log.page(log.by_team.get(&team_id).into_iter().flatten().rev(), offset, limit)
+    }))
+}
+
+pub fn get_user_activity(principal: Principal, offset: u64, limit: u64) -> Result<ActivityPage, Error> {
+    let caller_principal = msg_caller();
+    
+    // Users can only query their own activity
+    if caller_principal != principal {
+        return Err(Error::Unauthorized);
+    }
+    
+    Ok(crate::ACTIVITY_LOG.with(|log| {
+        let log = log.borrow();
+        log.page(log.by_actor.get(&principal).into_iter().flatten().rev(), offset, limit)
+    }))
+}
+
+// Retention settings, restricted to canister controllers
+pub fn get_activity_retention() -> ActivityRetention {
+    crate::ACTIVITY_RETENTION.with(|retention| retention.borrow().clone())
+}
+
+pub fn set_activity_retention(retention: ActivityRetention) -> Result<(), Error> {
+    if !ic_cdk::api::is_controller(&msg_caller()) {
+        return Err(Error::Unauthorized);
+    }
+    
+    if retention.max_entries_per_scope == 0 || retention.max_total_entries == 0 {
+        return Err(Error::InvalidInput("Retention must keep at least one entry".to_string()));
+    }
+    
+    crate::ACTIVITY_LOG.with(|log| log.borrow_mut().apply_retention(&retention));
+    crate::ACTIVITY_RETENTION.with(|current| *current.borrow_mut() = retention);
+    Ok(())
+}
+
+// Helper functions
+
+// Entries are bucketed by their narrowest scope so a busy project cannot evict anyone else's history
+fn scope_key(entry: &ActivityEntry) -> String {
+    match (&entry.project_id, &entry.team_id, &entry.entity) {
+        (Some(project_id), _, _) => format!("project:{}", project_id),
+        (None, Some(team_id), _) => format!("team:{}", team_id),
+        (None, None, ActivityEntity::User(principal)) => format!("user:{}", principal.to_text()),
+        (None, None, _) => format!("user:{}", entry.actor.to_text()),
+    }
+}
+
+impl ActivityLog {
+    fn push(&mut self, entry: ActivityEntry, retention: &ActivityRetention) {
+        let id = entry.id;
+        let key = scope_key(&entry);
+        if let Some(team_id) = &entry.team_id {
+            self.by_team.entry(team_id.clone()).or_default().insert(id);
+        }
+        self.by_actor.entry(entry.actor).or_default().insert(id);
+        self.entries.insert(id, entry);
+        
+        let bucket = self.by_scope.entry(key.clone()).or_default();
+        bucket.push_back(id);
+        let overflow = bucket.len().saturating_sub(retention.max_entries_per_scope as usize);
+        let evicted: Vec<ActivityId> = bucket.drain(..overflow).collect();
+        for id in evicted {
+            self.remove(id);
+        }
+        self.trim_total(retention.max_total_entries);
+    }
+    
+    fn apply_retention(&mut self, retention: &ActivityRetention) {
+        let evicted: Vec<ActivityId> = self
+            .by_scope
+            .values_mut()
+            .flat_map(|bucket| {
+                let overflow = bucket.len().saturating_sub(retention.max_entries_per_scope as usize);
+                bucket.drain(..overflow).collect::<Vec<_>>()
+            })
+            .collect();
+        for id in evicted {
+            self.remove(id);
+        }
+        self.by_scope.retain(|_, bucket| !bucket.is_empty());
+        self.trim_total(retention.max_total_entries);
+    }
+    
+    // Buckets of deleted projects and teams never grow again, so they age out here
+    fn trim_total(&mut self, max_total: u64) {
+        while self.entries.len() as u64 > max_total {
+            let Some((&id, entry)) = self.entries.first_key_value() else {
+                break;
+            };
+            let key = scope_key(entry);
+            if let Some(bucket) = self.by_scope.get_mut(&key) {
+                // The oldest entry overall is also the oldest in its bucket
+                if bucket.front() == Some(&id) {
+                    bucket.pop_front();
+                }
+                if bucket.is_empty() {
+                    self.by_scope.remove(&key);
+                }
+            }
+            self.remove(id);
+        }
+    }
+    
+    // Removes an entry and its team and actor index entries; the caller handles its scope bucket
+    fn remove(&mut self, id: ActivityId) {
+        let Some(entry) = self.entries.remove(&id) else {
+            return;
+        };
+        if let Some(team_id) = &entry.team_id {
+            if let Some(ids) = self.by_team.get_mut(team_id) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.by_team.remove(team_id);
+                }
+            }
+        }
+        if let Some(ids) = self.by_actor.get_mut(&entry.actor) {
+            ids.remove(&id);
+            if ids.is_empty() {
+                self.by_actor.remove(&entry.actor);
+            }
+        }
+    }
+    
+    // `ids` must already be newest first
+    fn page<'a>(&self, ids: impl Iterator<Item = &'a ActivityId>, offset: u64, limit: u64) -> ActivityPage {
+        let limit = limit.min(MAX_PAGE_SIZE) as usize;
+        let ids: Vec<&ActivityId> = ids.collect();
+        
+        ActivityPage {
+            total: ids.len() as u64,
+            entries: ids
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit)
+                .filter_map(|id| self.entries.get(id).cloned())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn entry(id: ActivityId, project_id: Option<&str>, team_id: Option<&str>) -> ActivityEntry {
+        ActivityEntry {
+            id,
+            actor: Principal::anonymous(),
+            timestamp: 0,
+            entity: ActivityEntity::Team("team".to_string()),
+            action: ActivityAction::Updated,
+            team_id: team_id.map(str::to_string),
+            project_id: project_id.map(str::to_string),
+            changes: vec![],
+        }
+    }
+    
+    fn retention(max_entries_per_scope: u64, max_total_entries: u64) -> ActivityRetention {
+        ActivityRetention { max_entries_per_scope, max_total_entries }
+    }
+    
+    fn ids(page: ActivityPage) -> Vec<ActivityId> {
+        page.entries.iter().map(|entry| entry.id).collect()
+    }
+    
+    #[test]
+    fn retention_is_bounded_per_scope() {
+        let mut log = ActivityLog::default();
+        let retention = retention(2, 100);
+        log.push(entry(0, None, Some("quiet")), &retention);
+        for id in 1..=10 {
+            log.push(entry(id, Some("busy"), Some("quiet")), &retention);
+        }
+        
+        let busy: Vec<ActivityId> = log.by_scope["project:busy"].iter().copied().collect();
+        assert_eq!(busy, vec![9, 10]);
+        assert_eq!(log.by_scope["team:quiet"].len(), 1);
+        assert_eq!(log.entries.len(), 3);
+        assert_eq!(log.by_team["quiet"].len(), 3);
+    }
+    
+    #[test]
+    fn retention_is_bounded_in_total() {
+        let mut log = ActivityLog::default();
+        let retention = retention(100, 3);
+        for id in 0..10 {
+            log.push(entry(id, Some(&format!("project-{}", id)), None), &retention);
+        }
+        
+        assert_eq!(log.entries.keys().copied().collect::<Vec<_>>(), vec![7, 8, 9]);
+        assert_eq!(log.by_scope.len(), 3);
+        assert!(!log.by_scope.contains_key("project:0"));
+    }
+    
+    #[test]
+    fn team_and_actor_queries_use_indexes() {
+        let mut log = ActivityLog::default();
+        let retention = retention(100, 100);
+        log.push(entry(0, Some("p1"), Some("t1")), &retention);
+        log.push(entry(1, None, Some("t2")), &retention);
+        log.push(entry(2, None, Some("t1")), &retention);
+        
+        let team = log.page(log.by_team["t1"].iter().rev(), 0, 10);
+        assert_eq!(team.total, 2);
+        assert_eq!(ids(team), vec![2, 0]);
+        
+        let actor = log.page(log.by_actor[&Principal::anonymous()].iter().rev(), 1, 1);
+        assert_eq!(actor.total, 3);
+        assert_eq!(ids(actor), vec![1]);
+    }
+}
@@ -0,0 +1,106 @@
+use candid::Principal;
+use ic_cdk::api::msg_caller;
+use serde::Serialize;
+use std::result::Result;
+
+use crate::types::*;
+use crate::project;
+
+const ARCHIVE_FORMAT: &str = "kanban-project-archive";
+const ARCHIVE_VERSION: u32 = 1;
+const CHUNK_SIZE: usize = 1024 * 1024; // stays well below the reply size limit
+
+// Self-describing archive layout, bump ARCHIVE_VERSION on breaking changes
+#[derive(Serialize)]
+struct ProjectArchive<'a> {
+    format: &'static str,
+    version: u32,
+    project: ArchivedProject<'a>,
+    members: Vec<ArchivedMember>,
+}
+
+#[derive(Serialize)]
+struct ArchivedProject<'a> {
+    id: &'a str,
+    name: &'a str,
+    description: &'a str,
+    owner: &'a Owner,
+    status: &'a ProjectStatus,
+    is_template: bool,
+    created_at: Timestamp,
+    updated_at: Timestamp,
+}
+
+// Usernames let members be matched up on another deployment
+#[derive(Serialize)]
+struct ArchivedMember {
+    principal: Principal,
+    username: Option<String>,
+    role: Role,
+    joined_at: Timestamp,
+}
+
+// Project export
+pub fn export_project(project_id: String, offset: u64) -> Result<ExportChunk, Error> {
+    let caller_principal = msg_caller();
+    
+    let project = crate::PROJECTS.with(|projects| projects.borrow().get(&project_id).cloned())
+        .ok_or(Error::ProjectNotFound)?;
+    
+    // Only Owners can export a project
+    let user_role = project::get_user_role_in_project(&project, &caller_principal)?;
+    if user_role != Role::Owner {
+        return Err(Error::InsufficientPermissions);
+    }
+    
+    let archive = build_archive(&project)?;
+    let total_size = archive.len() as u64;
+    if offset > total_size {
+        return Err(Error::InvalidInput("Offset is past the end of the archive".to_string()));
+    }
+    
+    let start = offset as usize;
+    let end = (start + CHUNK_SIZE).min(archive.len());
+    
+    Ok(ExportChunk {
+        data: archive[start..end].to_vec(),
+        offset,
+        total_size,
+        snapshot: project.updated_at,
+    })
+}
+
+// Helper functions
+fn build_archive(project: &Project) -> Result<Vec<u8>, Error> {
+    let members = crate::USERS.with(|users| {
+        let users = users.borrow();
+        project
+            .members
+            .iter()
+            .map(|member| ArchivedMember {
+                principal: member.principal,
+                username: users.get(&member.principal).map(|user| user.profile.username.clone()),
+                role: member.role.clone(),
+                joined_at: member.joined_at,
+            })
+            .collect()
+    });
+    
+    let archive = ProjectArchive {
+        format: ARCHIVE_FORMAT,
+        version: ARCHIVE_VERSION,
+        project: ArchivedProject {
+            id: &project.id,
+            name: &project.name,
+            description: &project.description,
+            owner: &project.owner,
+            status: &project.status,
+            is_template: project.is_template,
+            created_at: project.created_at,
+            updated_at: project.updated_at,
+        },
+        members,
+    };
+    
+    serde_json::to_vec(&archive).map_err(|err| Error::InternalError(err.to_string()))
+}
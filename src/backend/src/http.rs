@@ -0,0 +1,283 @@
+use candid::Principal;
+use ic_cdk::api::time;
+use ic_cdk::api::msg_caller;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::result::Result;
+
+use crate::types::*;
+use crate::utils;
+
+const MAX_TOKENS_PER_USER: usize = 10;
+
+// API token management
+pub async fn create_api_token(name: String) -> Result<String, Error> {
+    let caller_principal = msg_caller();
+    
+    if name.trim().is_empty() {
+        return Err(Error::InvalidInput("Token name cannot be empty".to_string()));
+    }
+    ensure_can_create_token(&caller_principal)?;
+    
+    let token_id = utils::generate_id().await;
+    let secret = utils::generate_id().await;
+    
+    // Other calls may have run during the awaits, so check again before inserting
+    ensure_can_create_token(&caller_principal)?;
+    
+    let token = ApiToken {
+        id: token_id,
+        name,
+        owner: caller_principal,
+        created_at: time(),
+    };
+    
+    // Only a hash of the secret is kept
+    crate::API_TOKENS.with(|tokens| {
+        tokens.borrow_mut().insert(hash_token(&secret), token);
+    });
+    
+    Ok(secret)
+}
+
+pub fn get_api_tokens() -> Vec<ApiToken> {
+    let caller_principal = msg_caller();
+    
+    crate::API_TOKENS.with(|tokens| {
+        tokens
+            .borrow()
+            .values()
+            .filter(|token| token.owner == caller_principal)
+            .cloned()
+            .collect()
+    })
+}
+
+pub async fn revoke_api_token(token_id: String) -> Result<(), Error> {
+    let caller_principal = msg_caller();
+    
+    crate::API_TOKENS.with(|tokens| {
+        let mut tokens = tokens.borrow_mut();
+        let hash = tokens
+            .iter()
+            .find(|(_, token)| token.id == token_id && token.owner == caller_principal)
+            .map(|(hash, _)| hash.clone())
+            .ok_or(Error::NotFound)?;
+        tokens.remove(&hash);
+        Ok(())
+    })
+}
+
+// Read-only JSON API
+// Uncertified query responses are rejected by the HTTP gateway, so every request is
+// upgraded to an update call, whose response is certified by consensus
+pub fn http_request(_request: HttpRequest) -> HttpResponse {
+    HttpResponse {
+        status_code: 200,
+        headers: vec![],
+        body: vec![],
+        upgrade: Some(true),
+    }
+}
+
+pub fn http_request_update(request: HttpRequest) -> HttpResponse {
+    if request.method != "GET" {
+        return error_response(405, "Method not allowed");
+    }
+    
+    let (path, include_archived) = parse_url(&request.url);
+    
+    // Requests without a token are served anonymously
+    let principal = match bearer_token(&request.headers) {
+        Some(secret) => match authenticate(secret) {
+            Some(principal) => Some(principal),
+            None => return error_response(401, "Invalid API token"),
+        },
+        None => None,
+    };
+    
+    // Anonymous callers can read public teams, as get_public_teams allows; project lists stay members-only
+    match parse_route(path) {
+        Some(Route::Project(project_id)) => match principal.and_then(|principal| find_project(project_id, &principal)) {
+            Some(project) => json_response(&project),
+            None => not_found(principal),
+        },
+        Some(Route::Team(team_id)) => match find_team(team_id, principal.as_ref()) {
+            Some(team) => json_response(&team),
+            None => not_found(principal),
+        },
+        Some(Route::TeamProjects(team_id)) => match principal.and_then(|principal| find_team_projects(team_id, &principal, include_archived)) {
+            Some(projects) => json_response(&projects),
+            None => not_found(principal),
+        },
+        None => error_response(404, "Not found"),
+    }
+}
+
+// Routes served by the JSON API
+#[derive(Debug, PartialEq)]
+enum Route<'a> {
+    Project(&'a str),
+    Team(&'a str),
+    TeamProjects(&'a str),
+}
+
+// Helper functions
+fn ensure_can_create_token(principal: &Principal) -> Result<(), Error> {
+    // Ensure user exists
+    if !crate::USERS.with(|users| users.borrow().contains_key(principal)) {
+        return Err(Error::Unauthorized);
+    }
+    
+    let token_count = crate::API_TOKENS.with(|tokens| {
+        tokens.borrow().values().filter(|token| token.owner == *principal).count()
+    });
+    if token_count >= MAX_TOKENS_PER_USER {
+        return Err(Error::InvalidInput("Too many API tokens".to_string()));
+    }
+    Ok(())
+}
+
+fn parse_url(url: &str) -> (&str, bool) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let include_archived = query.split('&').any(|param| param == "include_archived=true");
+    (path, include_archived)
+}
+
+fn parse_route(path: &str) -> Option<Route<'_>> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["api", "projects", project_id] if !project_id.is_empty() => Some(Route::Project(project_id)),
+        ["api", "teams", team_id] if !team_id.is_empty() => Some(Route::Team(team_id)),
+        ["api", "teams", team_id, "projects"] if !team_id.is_empty() => Some(Route::TeamProjects(team_id)),
+        _ => None,
+    }
+}
+
+fn hash_token(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn authenticate(secret: &str) -> Option<Principal> {
+    crate::API_TOKENS.with(|tokens| tokens.borrow().get(&hash_token(secret)).map(|token| token.owner))
+}
+
+fn bearer_token(headers: &[(String, String)]) -> Option<&str> {
+    headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+        .and_then(|(_, value)| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+fn find_project(project_id: &str, principal: &Principal) -> Option<Project> {
+    crate::PROJECTS.with(|projects| {
+        projects
+            .borrow()
+            .get(project_id)
+            .filter(|project| project.members.iter().any(|member| member.principal == *principal))
+            .cloned()
+    })
+}
+
+fn find_team(team_id: &str, principal: Option<&Principal>) -> Option<Team> {
+    crate::TEAMS.with(|teams| {
+        teams
+            .borrow()
+            .get(team_id)
+            .filter(|team| {
+                // Public teams are readable anonymously
+                team.is_public
+                    || principal.is_some_and(|principal| team.members.iter().any(|member| member.principal == *principal))
+            })
+            .cloned()
+    })
+}
+
+fn find_team_projects(team_id: &str, principal: &Principal, include_archived: bool) -> Option<Vec<Project>> {
+    let is_team_member = crate::TEAMS.with(|teams| {
+        teams
+            .borrow()
+            .get(team_id)
+            .is_some_and(|team| team.members.iter().any(|member| member.principal == *principal))
+    });
+    if !is_team_member {
+        return None;
+    }
+    
+    Some(crate::PROJECTS.with(|projects| {
+        projects
+            .borrow()
+            .values()
+            .filter(|project| matches!(project.owner, Owner::Team(ref owner_team_id) if owner_team_id == team_id))
+            .filter(|project| include_archived || project.status != ProjectStatus::Archived)
+            .cloned()
+            .collect()
+    }))
+}
+
+fn json_response<T: Serialize>(value: &T) -> HttpResponse {
+    match serde_json::to_vec(value) {
+        Ok(body) => HttpResponse {
+            status_code: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body,
+            upgrade: None,
+        },
+        Err(_) => error_response(500, "Failed to encode response"),
+    }
+}
+
+// Resources the caller cannot see are reported as missing, like the Candid queries do
+fn not_found(principal: Option<Principal>) -> HttpResponse {
+    match principal {
+        Some(_) => error_response(404, "Not found"),
+        None => error_response(401, "Authentication required"),
+    }
+}
+
+fn error_response(status_code: u16, message: &str) -> HttpResponse {
+    HttpResponse {
+        status_code,
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: serde_json::json!({ "error": message }).to_string().into_bytes(),
+        upgrade: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn header(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+    
+    #[test]
+    fn bearer_token_is_read_from_authorization() {
+        assert_eq!(bearer_token(&[header("Authorization", "Bearer secret")]), Some("secret"));
+        assert_eq!(bearer_token(&[header("authorization", "Bearer  secret ")]), Some("secret"));
+        assert_eq!(bearer_token(&[header("Authorization", "Basic secret")]), None);
+        assert_eq!(bearer_token(&[header("X-Token", "Bearer secret")]), None);
+        assert_eq!(bearer_token(&[]), None);
+    }
+    
+    #[test]
+    fn routes_are_matched_by_segment() {
+        assert_eq!(parse_route("/api/projects/p1"), Some(Route::Project("p1")));
+        assert_eq!(parse_route("/api/teams/t1/"), Some(Route::Team("t1")));
+        assert_eq!(parse_route("/api/teams/t1/projects"), Some(Route::TeamProjects("t1")));
+        assert_eq!(parse_route("/api/projects"), None);
+        assert_eq!(parse_route("/api/projects//"), None);
+        assert_eq!(parse_route("/api/teams/t1/members"), None);
+        assert_eq!(parse_route("/projects/p1"), None);
+    }
+    
+    #[test]
+    fn query_string_is_split_from_path() {
+        assert_eq!(parse_url("/api/teams/t1/projects?include_archived=true"), ("/api/teams/t1/projects", true));
+        assert_eq!(parse_url("/api/teams/t1/projects?x=1&include_archived=true"), ("/api/teams/t1/projects", true));
+        assert_eq!(parse_url("/api/teams/t1/projects?include_archived=false"), ("/api/teams/t1/projects", false));
+        assert_eq!(parse_url("/api/projects/p1"), ("/api/projects/p1", false));
+    }
+}
@@ -7,6 +7,8 @@ use crate::types::*;
 use crate::utils;
 use crate::team;
 use crate::project;
+use crate::activity::{self, ActivityScope};
+use crate::notification;
 
 // Invitation management
 pub async fn invite_user(target: InviteTarget, role: Role, invited_user: Principal) -> Result<String, Error> {
@@ -22,6 +24,40 @@ pub async fn invite_user(target: InviteTarget, role: Role, invited_user: Princip
         return Err(Error::UserNotFound);
     }
     
+    // Only owners and managers of the target can invite
+    let user_role = match &target {
+        InviteTarget::Team(team_id) => crate::TEAMS.with(|teams| {
+            let teams = teams.borrow();
+            let team = teams.get(team_id).ok_or(Error::TeamNotFound)?;
+            team::get_user_role_in_team(team, &caller_principal)
+        })?,
+        InviteTarget::Project(project_id) => crate::PROJECTS.with(|projects| {
+            let projects = projects.borrow();
+            let project = projects.get(project_id).ok_or(Error::ProjectNotFound)?;
+            // Archived projects are read-only and cannot take new members
+            if project.status == ProjectStatus::Archived {
+                return Err(Error::ProjectArchived);
+            }
+            project::get_user_role_in_project(project, &caller_principal).or_else(|err| match &project.owner {
+                // Owners and managers of the owning team can staff its projects
+                Owner::Team(team_id) => crate::TEAMS.with(|teams| {
+                    let teams = teams.borrow();
+                    let team = teams.get(team_id).ok_or(Error::TeamNotFound)?;
+                    team::get_user_role_in_team(team, &caller_principal)
+                }),
+                Owner::User(_) => Err(err),
+            })
+        })?,
+    };
+    if user_role != Role::Owner && user_role != Role::Manager {
+        return Err(Error::InsufficientPermissions);
+    }
+    
+    // Nobody can hand out a role above their own
+    if role_rank(&role) > role_rank(&user_role) {
+        return Err(Error::InsufficientPermissions);
+    }
+    
     let invite_id = utils::generate_id().await;
     let now = time();
     
@@ -35,10 +71,19 @@ pub async fn invite_user(target: InviteTarget, role: Role, invited_user: Princip
         created_at: now,
     };
     
+    let scope = invite_scope(&invite.target);
+    let notification_target = invite.target.clone();
+    let changes = vec![
+        activity::change("invited_user", None, Some(invited_user.to_text())),
+        activity::change("role", None, Some(activity::role_value(&invite.role))),
+    ];
     crate::INVITES.with(|invites| {
         invites.borrow_mut().insert(invite_id.clone(), invite);
     });
     
+    activity::record(ActivityEntity::Invite(invite_id.clone()), ActivityAction::InviteSent, scope, changes);
+    notification::notify(invited_user, NotificationKind::InviteReceived, notification_target, Some(invite_id.clone()));
+    
     Ok(invite_id)
 }
 
@@ -74,6 +119,18 @@ pub async fn accept_invite(invite_id: String) -> Result<(), Error> {
             
             // Update invite status to accepted
             invite.status = InviteStatus::Accepted;
+            activity::record(
+                ActivityEntity::Invite(invite_id.clone()),
+                ActivityAction::InviteAccepted,
+                invite_scope(&invite.target),
+                vec![activity::change("status", Some("Pending".to_string()), Some("Accepted".to_string()))],
+            );
+            notification::notify(
+                invite.invited_by,
+                NotificationKind::InviteAccepted,
+                invite.target.clone(),
+                Some(invite_id.clone()),
+            );
             Ok(())
         } else {
             Err(Error::InviteNotFound)
@@ -103,6 +160,18 @@ pub async fn decline_invite(invite_id: String) -> Result<(), Error> {
             
             // Update invite status to declined
             invite.status = InviteStatus::Declined;
+            activity::record(
+                ActivityEntity::Invite(invite_id.clone()),
+                ActivityAction::InviteDeclined,
+                invite_scope(&invite.target),
+                vec![activity::change("status", Some("Pending".to_string()), Some("Declined".to_string()))],
+            );
+            notification::notify(
+                invite.invited_by,
+                NotificationKind::InviteDeclined,
+                invite.target.clone(),
+                Some(invite_id.clone()),
+            );
             Ok(())
         } else {
             Err(Error::InviteNotFound)
@@ -132,6 +201,12 @@ pub async fn cancel_invite(invite_id: String) -> Result<(), Error> {
             
             // Update invite status to cancelled
             invite.status = InviteStatus::Cancelled;
+            activity::record(
+                ActivityEntity::Invite(invite_id.clone()),
+                ActivityAction::InviteCancelled,
+                invite_scope(&invite.target),
+                vec![activity::change("status", Some("Pending".to_string()), Some("Cancelled".to_string()))],
+            );
             Ok(())
         } else {
             Err(Error::InviteNotFound)
@@ -149,7 +224,9 @@ pub async fn remove_member(target: InviteTarget, principal: Principal) -> Result
             if user_role != Role::Owner && user_role != Role::Manager {
                 return Err(Error::InsufficientPermissions);
             }
-            team::remove_user_from_team(&team_id, &principal)
+            team::remove_user_from_team(&team_id, &principal)?;
+            notification::notify(principal, NotificationKind::MemberRemoved, InviteTarget::Team(team_id), None);
+            Ok(())
         }
         InviteTarget::Project(project_id) => {
             let project = project::get_project(project_id.clone()).ok_or(Error::ProjectNotFound)?;
@@ -157,7 +234,9 @@ pub async fn remove_member(target: InviteTarget, principal: Principal) -> Result
             if user_role != Role::Owner && user_role != Role::Manager {
                 return Err(Error::InsufficientPermissions);
             }
-            project::remove_user_from_project(&project_id, &principal)
+            project::remove_user_from_project(&project_id, &principal)?;
+            notification::notify(principal, NotificationKind::MemberRemoved, InviteTarget::Project(project_id), None);
+            Ok(())
         }
     }
 }
@@ -212,4 +291,29 @@ pub fn get_pending_invites(principal: Principal) -> Vec<Invite> {
             .cloned()
             .collect()
     })
-} 
\ No newline at end of file
+}
+
+// Helper functions
+fn invite_scope(target: &InviteTarget) -> ActivityScope {
+    match target {
+        InviteTarget::Team(team_id) => ActivityScope::team(team_id),
+        InviteTarget::Project(project_id) => crate::PROJECTS.with(|projects| {
+            projects
+                .borrow()
+                .get(project_id)
+                .map(ActivityScope::project)
+                .unwrap_or_else(|| ActivityScope {
+                    team_id: None,
+                    project_id: Some(project_id.clone()),
+                })
+        }),
+    }
+}
+
+fn role_rank(role: &Role) -> u8 {
+    match role {
+        Role::Owner => 2,
+        Role::Manager => 1,
+        Role::Collaborator => 0,
+    }
+}
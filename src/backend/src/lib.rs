@@ -1,5 +1,6 @@
 use candid::Principal;
-use std::collections::HashMap;
+use ic_cdk::management_canister::{HttpRequestResult, TransformArgs};
+use std::collections::{HashMap, VecDeque};
 use std::cell::RefCell;
 use std::result::Result;
 
@@ -12,6 +13,11 @@ mod user;
 mod team;
 mod project;
 mod invite;
+mod activity;
+mod notification;
+mod webhook;
+mod http;
+mod export;
 
 // State management - using Principal as primary key
 thread_local! {
@@ -19,6 +25,34 @@ thread_local! {
     static TEAMS: RefCell<HashMap<String, Team>> = RefCell::new(HashMap::new());
     static PROJECTS: RefCell<HashMap<String, Project>> = RefCell::new(HashMap::new());
     static INVITES: RefCell<HashMap<String, Invite>> = RefCell::new(HashMap::new());
+    static ACTIVITY_LOG: RefCell<activity::ActivityLog> = RefCell::new(activity::ActivityLog::default());
+    static NEXT_ACTIVITY_ID: RefCell<ActivityId> = const { RefCell::new(0) };
+    static NOTIFICATIONS: RefCell<HashMap<Principal, Vec<Notification>>> = RefCell::new(HashMap::new());
+    static NEXT_NOTIFICATION_ID: RefCell<NotificationId> = const { RefCell::new(0) };
+    static PENDING_DIGESTS: RefCell<HashMap<Principal, Vec<DigestItem>>> = RefCell::new(HashMap::new());
+    static QUIET_HOURS_HELD: RefCell<HashMap<Principal, Vec<DigestItem>>> = RefCell::new(HashMap::new());
+    static LAST_DIGEST_DAY: RefCell<HashMap<Principal, u64>> = RefCell::new(HashMap::new());
+    static WEBHOOKS: RefCell<HashMap<String, Webhook>> = RefCell::new(HashMap::new());
+    static WEBHOOK_DELIVERIES: RefCell<HashMap<String, VecDeque<WebhookDelivery>>> = RefCell::new(HashMap::new());
+    static NEXT_WEBHOOK_DELIVERY_ID: RefCell<WebhookDeliveryId> = const { RefCell::new(0) };
+    static API_TOKENS: RefCell<HashMap<String, ApiToken>> = RefCell::new(HashMap::new());
+    static ACTIVITY_RETENTION: RefCell<ActivityRetention> = const {
+        RefCell::new(ActivityRetention {
+            max_entries_per_scope: activity::DEFAULT_MAX_ENTRIES_PER_SCOPE,
+            max_total_entries: activity::DEFAULT_MAX_TOTAL_ENTRIES,
+        })
+    };
+}
+
+// Canister lifecycle - timers do not survive upgrades and are restarted here
+#[ic_cdk::init]
+fn init() {
+    notification::start_notification_timer();
+}
+
+#[ic_cdk::post_upgrade]
+fn post_upgrade() {
+    notification::start_notification_timer();
 }
 
 // Public API endpoints - User management
@@ -42,6 +76,11 @@ async fn update_theme_preferences(theme_preferences: ThemePreferences) -> Result
     user::update_theme_preferences(theme_preferences).await
 }
 
+#[ic_cdk::update]
+async fn update_notification_preferences(notification_preferences: NotificationPreferences) -> Result<(), Error> {
+    user::update_notification_preferences(notification_preferences).await
+}
+
 #[ic_cdk::update]
 async fn update_username(principal: Principal, username: String) -> Result<(), Error> {
     user::update_username(principal, username).await
@@ -109,14 +148,39 @@ async fn transfer_ownership(project_id: String, new_owner: Owner) -> Result<(),
     project::transfer_ownership(project_id, new_owner).await
 }
 
+#[ic_cdk::update]
+async fn archive_project(project_id: String) -> Result<(), Error> {
+    project::archive_project(project_id).await
+}
+
+#[ic_cdk::update]
+async fn restore_project(project_id: String) -> Result<(), Error> {
+    project::restore_project(project_id).await
+}
+
+#[ic_cdk::update]
+async fn clone_project(project_id: String, new_name: String, owner: Owner, options: CloneProjectOptions) -> Result<String, Error> {
+    project::clone_project(project_id, new_name, owner, options).await
+}
+
 #[ic_cdk::query]
-fn get_user_projects(principal: Principal) -> Vec<Project> {
-    project::get_user_projects(principal)
+fn export_project(project_id: String, offset: u64) -> Result<ExportChunk, Error> {
+    export::export_project(project_id, offset)
 }
 
 #[ic_cdk::query]
-fn get_team_projects(team_id: String) -> Vec<Project> {
-    project::get_team_projects(team_id)
+fn get_user_projects(principal: Principal, include_archived: Option<bool>) -> Vec<Project> {
+    project::get_user_projects(principal, include_archived)
+}
+
+#[ic_cdk::query]
+fn get_team_projects(team_id: String, include_archived: Option<bool>) -> Vec<Project> {
+    project::get_team_projects(team_id, include_archived)
+}
+
+#[ic_cdk::query]
+fn get_team_project_templates(team_id: String) -> Vec<Project> {
+    project::get_team_project_templates(team_id)
 }
 
 // Public API endpoints - Invitation management
@@ -155,6 +219,110 @@ fn get_pending_invites(principal: Principal) -> Vec<Invite> {
     invite::get_pending_invites(principal)
 }
 
+// Public API endpoints - Activity log
+#[ic_cdk::query]
+fn get_project_activity(project_id: String, offset: u64, limit: u64) -> Result<ActivityPage, Error> {
+    activity::get_project_activity(project_id, offset, limit)
+}
+
+#[ic_cdk::query]
+fn get_team_activity(team_id: String, offset: u64, limit: u64) -> Result<ActivityPage, Error> {
+    activity::get_team_activity(team_id, offset, limit)
+}
+
+#[ic_cdk::query]
+fn get_user_activity(principal: Principal, offset: u64, limit: u64) -> Result<ActivityPage, Error> {
+    activity::get_user_activity(principal, offset, limit)
+}
+
+#[ic_cdk::query]
+fn get_activity_retention() -> ActivityRetention {
+    activity::get_activity_retention()
+}
+
+#[ic_cdk::update]
+fn set_activity_retention(retention: ActivityRetention) -> Result<(), Error> {
+    activity::set_activity_retention(retention)
+}
+
+// Public API endpoints - Notifications
+#[ic_cdk::query]
+fn get_notifications(offset: u64, limit: u64) -> NotificationPage {
+    notification::get_notifications(offset, limit)
+}
+
+#[ic_cdk::query]
+fn get_unread_notification_count() -> u64 {
+    notification::get_unread_notification_count()
+}
+
+#[ic_cdk::update]
+fn mark_notification_read(notification_id: NotificationId) -> Result<(), Error> {
+    notification::mark_notification_read(notification_id)
+}
+
+#[ic_cdk::update]
+fn mark_all_notifications_read() -> Result<(), Error> {
+    notification::mark_all_notifications_read()
+}
+
+// Public API endpoints - Webhooks
+#[ic_cdk::update]
+async fn register_webhook(project_id: String, url: String, event_filter: Vec<ActivityAction>) -> Result<String, Error> {
+    webhook::register_webhook(project_id, url, event_filter).await
+}
+
+#[ic_cdk::update]
+async fn update_webhook(webhook_id: String, updates: WebhookUpdate) -> Result<(), Error> {
+    webhook::update_webhook(webhook_id, updates).await
+}
+
+#[ic_cdk::update]
+async fn delete_webhook(webhook_id: String) -> Result<(), Error> {
+    webhook::delete_webhook(webhook_id).await
+}
+
+#[ic_cdk::query]
+fn get_project_webhooks(project_id: String) -> Result<Vec<Webhook>, Error> {
+    webhook::get_project_webhooks(project_id)
+}
+
+#[ic_cdk::query]
+fn get_webhook_deliveries(webhook_id: String) -> Result<Vec<WebhookDelivery>, Error> {
+    webhook::get_webhook_deliveries(webhook_id)
+}
+
+#[ic_cdk::query(hidden = true)]
+fn transform_webhook_response(args: TransformArgs) -> HttpRequestResult {
+    webhook::transform_response(args)
+}
+
+// Public API endpoints - HTTP API
+#[ic_cdk::update]
+async fn create_api_token(name: String) -> Result<String, Error> {
+    http::create_api_token(name).await
+}
+
+#[ic_cdk::query]
+fn get_api_tokens() -> Vec<ApiToken> {
+    http::get_api_tokens()
+}
+
+#[ic_cdk::update]
+async fn revoke_api_token(token_id: String) -> Result<(), Error> {
+    http::revoke_api_token(token_id).await
+}
+
+#[ic_cdk::query]
+fn http_request(request: HttpRequest) -> HttpResponse {
+    http::http_request(request)
+}
+
+#[ic_cdk::update]
+fn http_request_update(request: HttpRequest) -> HttpResponse {
+    http::http_request_update(request)
+}
+
 // Utility functions
 #[ic_cdk::query]
 fn health_check() -> String {
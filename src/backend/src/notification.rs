@@ -0,0 +1,401 @@
+use candid::Principal;
+use ic_cdk::api::time;
+use ic_cdk::api::msg_caller;
+use std::result::Result;
+use std::time::Duration;
+
+use crate::types::*;
+
+const MAX_NOTIFICATIONS_PER_USER: usize = 500;
+const MAX_PAGE_SIZE: u64 = 100;
+const MINUTES_PER_DAY: u16 = 24 * 60;
+const MIN_UTC_OFFSET_MINUTES: i16 = -12 * 60;
+const MAX_UTC_OFFSET_MINUTES: i16 = 14 * 60;
+const DIGEST_HOUR: u16 = 8; // local time at which daily digests are built
+const MAX_QUEUED_ITEMS_PER_USER: usize = 100; // per digest and per quiet hours hold
+const TICK_MINUTES: u64 = 5;
+const NANOS_PER_MINUTE: u64 = 60_000_000_000;
+
+// Delivery
+pub fn notify(recipient: Principal, kind: NotificationKind, target: InviteTarget, invite_id: Option<InviteId>) {
+    let actor = msg_caller();
+    
+    // Users are not notified about their own actions
+    if recipient == actor {
+        return;
+    }
+    
+    let now = time();
+    let preferences = crate::USERS.with(|users| {
+        users
+            .borrow()
+            .get(&recipient)
+            .and_then(|user| user.profile.notification_preferences.clone())
+    });
+    
+    let item = DigestItem {
+        kind,
+        actor,
+        target,
+        invite_id,
+        created_at: now,
+    };
+    
+    match delivery_for(preferences.as_ref(), &item.kind) {
+        NotificationDelivery::Off => {}
+        NotificationDelivery::Digest => {
+            crate::PENDING_DIGESTS.with(|pending| {
+                push_capped(pending.borrow_mut().entry(recipient).or_default(), item);
+            });
+        }
+        // Held until the recipient's quiet hours end
+        NotificationDelivery::Immediate if in_quiet_hours(preferences.as_ref(), now) => {
+            crate::QUIET_HOURS_HELD.with(|held| {
+                push_capped(held.borrow_mut().entry(recipient).or_default(), item);
+            });
+        }
+        NotificationDelivery::Immediate => {
+            push(recipient, item.kind, item.actor, Some(item.target), item.invite_id, vec![]);
+        }
+    }
+}
+
+// Preferences
+pub fn validate_preferences(preferences: &NotificationPreferences) -> Result<(), Error> {
+    if !(MIN_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&preferences.utc_offset_minutes) {
+        return Err(Error::InvalidInput("UTC offset out of range".to_string()));
+    }
+    
+    if let Some(quiet_hours) = &preferences.quiet_hours {
+        if quiet_hours.start_minute >= MINUTES_PER_DAY || quiet_hours.end_minute >= MINUTES_PER_DAY {
+            return Err(Error::InvalidInput("Quiet hours must be within a day".to_string()));
+        }
+    }
+    
+    if preferences.deliveries.iter().any(|preference| preference.kind == NotificationKind::DailyDigest) {
+        return Err(Error::InvalidInput("Daily digests cannot be configured per kind".to_string()));
+    }
+    
+    Ok(())
+}
+
+// Queued notifications
+pub fn start_notification_timer() {
+    // Short ticks so held notifications and digests go out soon after they become due
+    ic_cdk_timers::set_timer_interval(Duration::from_secs(TICK_MINUTES * 60), process_queues);
+}
+
+fn process_queues() {
+    let now = time();
+    release_quiet_hours_held(now);
+    build_digests(now);
+}
+
+fn release_quiet_hours_held(now: Timestamp) {
+    let due: Vec<Principal> = crate::QUIET_HOURS_HELD.with(|held| {
+        held.borrow()
+            .keys()
+            .filter(|principal| !in_quiet_hours(preferences_of(principal).as_ref(), now))
+            .cloned()
+            .collect()
+    });
+    
+    for recipient in due {
+        let items = crate::QUIET_HOURS_HELD.with(|held| held.borrow_mut().remove(&recipient)).unwrap_or_default();
+        for item in items {
+            push(recipient, item.kind, item.actor, Some(item.target), item.invite_id, vec![]);
+        }
+    }
+}
+
+fn build_digests(now: Timestamp) {
+    let due: Vec<Principal> = crate::PENDING_DIGESTS.with(|pending| {
+        pending
+            .borrow()
+            .iter()
+            .filter(|(principal, items)| {
+                let Some(oldest) = items.first() else {
+                    return false;
+                };
+                let last_day = crate::LAST_DIGEST_DAY.with(|last| last.borrow().get(*principal).copied());
+                digest_due(now, preferences_of(principal).as_ref(), last_day, oldest.created_at)
+            })
+            .map(|(principal, _)| *principal)
+            .collect()
+    });
+    
+    for recipient in due {
+        let offset = preferences_of(&recipient).map(|preferences| preferences.utc_offset_minutes).unwrap_or(0);
+        crate::LAST_DIGEST_DAY.with(|last| last.borrow_mut().insert(recipient, local_day(now, offset)));
+        
+        let items = crate::PENDING_DIGESTS.with(|pending| pending.borrow_mut().remove(&recipient));
+        if let Some(items) = items.filter(|items| !items.is_empty()) {
+            push(recipient, NotificationKind::DailyDigest, ic_cdk::api::canister_self(), None, None, items);
+        }
+    }
+}
+
+// Inbox management
+pub fn get_notifications(offset: u64, limit: u64) -> NotificationPage {
+    let caller_principal = msg_caller();
+    let limit = limit.min(MAX_PAGE_SIZE) as usize;
+    
+    crate::NOTIFICATIONS.with(|notifications| {
+        let notifications = notifications.borrow();
+        let inbox = notifications.get(&caller_principal).map(Vec::as_slice).unwrap_or_default();
+        NotificationPage {
+            total: inbox.len() as u64,
+            notifications: inbox
+                .iter()
+                .rev()
+                .skip(offset as usize)
+                .take(limit)
+                .cloned()
+                .collect(),
+        }
+    })
+}
+
+pub fn get_unread_notification_count() -> u64 {
+    let caller_principal = msg_caller();
+    
+    crate::NOTIFICATIONS.with(|notifications| {
+        notifications
+            .borrow()
+            .get(&caller_principal)
+            .map(|inbox| inbox.iter().filter(|notification| !notification.read).count() as u64)
+            .unwrap_or(0)
+    })
+}
+
+pub fn mark_notification_read(notification_id: NotificationId) -> Result<(), Error> {
+    let caller_principal = msg_caller();
+    
+    crate::NOTIFICATIONS.with(|notifications| {
+        let mut notifications = notifications.borrow_mut();
+        let notification = notifications
+            .get_mut(&caller_principal)
+            .and_then(|inbox| inbox.iter_mut().find(|notification| notification.id == notification_id))
+            .ok_or(Error::NotFound)?;
+        notification.read = true;
+        Ok(())
+    })
+}
+
+pub fn mark_all_notifications_read() -> Result<(), Error> {
+    let caller_principal = msg_caller();
+    
+    crate::NOTIFICATIONS.with(|notifications| {
+        if let Some(inbox) = notifications.borrow_mut().get_mut(&caller_principal) {
+            inbox.iter_mut().for_each(|notification| notification.read = true);
+        }
+    });
+    Ok(())
+}
+
+// Helper functions
+fn preferences_of(principal: &Principal) -> Option<NotificationPreferences> {
+    crate::USERS.with(|users| {
+        users
+            .borrow()
+            .get(principal)
+            .and_then(|user| user.profile.notification_preferences.clone())
+    })
+}
+
+fn delivery_for(preferences: Option<&NotificationPreferences>, kind: &NotificationKind) -> NotificationDelivery {
+    preferences
+        .and_then(|preferences| preferences.deliveries.iter().find(|preference| preference.kind == *kind))
+        .map(|preference| preference.delivery.clone())
+        .unwrap_or(NotificationDelivery::Immediate)
+}
+
+fn in_quiet_hours(preferences: Option<&NotificationPreferences>, now: Timestamp) -> bool {
+    let Some(preferences) = preferences else {
+        return false;
+    };
+    
+    preferences.quiet_hours.as_ref().is_some_and(|quiet_hours| {
+        let minute = local_minute(now, preferences.utc_offset_minutes);
+        if quiet_hours.start_minute <= quiet_hours.end_minute {
+            minute >= quiet_hours.start_minute && minute < quiet_hours.end_minute
+        } else {
+            // Window wraps past midnight, e.g. 22:00-07:00
+            minute >= quiet_hours.start_minute || minute < quiet_hours.end_minute
+        }
+    })
+}
+
+// Once per local day, from DIGEST_HOUR on, outside quiet hours. Items queued after
+// today's DIGEST_HOUR wait for tomorrow's digest.
+fn digest_due(now: Timestamp, preferences: Option<&NotificationPreferences>, last_day: Option<u64>, oldest_item_at: Timestamp) -> bool {
+    let offset = preferences.map(|preferences| preferences.utc_offset_minutes).unwrap_or(0);
+    let today = local_day(now, offset);
+    let digest_time = today as i64 * MINUTES_PER_DAY as i64 + DIGEST_HOUR as i64 * 60;
+    
+    local_minutes(now, offset) >= digest_time
+        && local_minutes(oldest_item_at, offset) < digest_time
+        && last_day != Some(today)
+        && !in_quiet_hours(preferences, now)
+}
+
+fn local_minute(now: Timestamp, utc_offset_minutes: i16) -> u16 {
+    local_minutes(now, utc_offset_minutes).rem_euclid(MINUTES_PER_DAY as i64) as u16
+}
+
+fn local_day(now: Timestamp, utc_offset_minutes: i16) -> u64 {
+    local_minutes(now, utc_offset_minutes).div_euclid(MINUTES_PER_DAY as i64) as u64
+}
+
+fn local_minutes(now: Timestamp, utc_offset_minutes: i16) -> i64 {
+    (now / NANOS_PER_MINUTE) as i64 + utc_offset_minutes as i64
+}
+
+// Oldest queued items are dropped first
+fn push_capped(items: &mut Vec<DigestItem>, item: DigestItem) {
+    items.push(item);
+    if items.len() > MAX_QUEUED_ITEMS_PER_USER {
+        let overflow = items.len() - MAX_QUEUED_ITEMS_PER_USER;
+        items.drain(..overflow);
+    }
+}
+
+fn push(
+    recipient: Principal,
+    kind: NotificationKind,
+    actor: Principal,
+    target: Option<InviteTarget>,
+    invite_id: Option<InviteId>,
+    digest: Vec<DigestItem>,
+) {
+    let id = crate::NEXT_NOTIFICATION_ID.with(|next_id| {
+        let mut next_id = next_id.borrow_mut();
+        let id = *next_id;
+        *next_id += 1;
+        id
+    });
+    
+    let notification = Notification {
+        id,
+        kind,
+        actor,
+        target,
+        invite_id,
+        digest,
+        read: false,
+        created_at: time(),
+    };
+    
+    crate::NOTIFICATIONS.with(|notifications| {
+        let mut notifications = notifications.borrow_mut();
+        let inbox = notifications.entry(recipient).or_default();
+        inbox.push(notification);
+        if inbox.len() > MAX_NOTIFICATIONS_PER_USER {
+            let overflow = inbox.len() - MAX_NOTIFICATIONS_PER_USER;
+            inbox.drain(..overflow);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const HOUR: u64 = 60 * NANOS_PER_MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    
+    fn preferences(quiet_hours: Option<(u16, u16)>, utc_offset_minutes: i16) -> NotificationPreferences {
+        NotificationPreferences {
+            deliveries: vec![NotificationPreference {
+                kind: NotificationKind::InviteAccepted,
+                delivery: NotificationDelivery::Digest,
+            }],
+            quiet_hours: quiet_hours.map(|(start_minute, end_minute)| QuietHours { start_minute, end_minute }),
+            utc_offset_minutes,
+        }
+    }
+    
+    fn item() -> DigestItem {
+        DigestItem {
+            kind: NotificationKind::InviteReceived,
+            actor: Principal::anonymous(),
+            target: InviteTarget::Team("team".to_string()),
+            invite_id: None,
+            created_at: 0,
+        }
+    }
+    
+    #[test]
+    fn local_minute_applies_offset_and_wraps() {
+        assert_eq!(local_minute(10 * HOUR, 0), 600);
+        assert_eq!(local_minute(10 * HOUR, 120), 720);
+        assert_eq!(local_minute(HOUR, -120), 23 * 60);
+        assert_eq!(local_minute(23 * HOUR, 120), 60);
+        assert_eq!(local_day(DAY + HOUR, -120), 0);
+    }
+    
+    #[test]
+    fn delivery_defaults_to_immediate() {
+        let preferences = preferences(None, 0);
+        assert!(delivery_for(None, &NotificationKind::InviteReceived) == NotificationDelivery::Immediate);
+        assert!(delivery_for(Some(&preferences), &NotificationKind::InviteReceived) == NotificationDelivery::Immediate);
+        assert!(delivery_for(Some(&preferences), &NotificationKind::InviteAccepted) == NotificationDelivery::Digest);
+    }
+    
+    #[test]
+    fn quiet_hours_within_a_day() {
+        let preferences = preferences(Some((9 * 60, 17 * 60)), 0);
+        assert!(!in_quiet_hours(Some(&preferences), 8 * HOUR));
+        assert!(in_quiet_hours(Some(&preferences), 9 * HOUR));
+        assert!(in_quiet_hours(Some(&preferences), 16 * HOUR));
+        assert!(!in_quiet_hours(Some(&preferences), 17 * HOUR));
+        assert!(!in_quiet_hours(None, 12 * HOUR));
+    }
+    
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        let preferences = preferences(Some((22 * 60, 7 * 60)), 0);
+        assert!(in_quiet_hours(Some(&preferences), 23 * HOUR));
+        assert!(in_quiet_hours(Some(&preferences), DAY + 3 * HOUR));
+        assert!(!in_quiet_hours(Some(&preferences), DAY + 7 * HOUR));
+        assert!(!in_quiet_hours(Some(&preferences), 12 * HOUR));
+        
+        // 20:00 UTC is 22:00 at UTC+2
+        let shifted = self::preferences(Some((22 * 60, 7 * 60)), 120);
+        assert!(in_quiet_hours(Some(&shifted), 20 * HOUR));
+    }
+    
+    #[test]
+    fn digest_goes_out_once_per_local_day() {
+        assert!(!digest_due(7 * HOUR, None, None, 0));
+        assert!(digest_due(8 * HOUR, None, None, 0));
+        assert!(!digest_due(8 * HOUR + 30 * NANOS_PER_MINUTE, None, Some(0), 0));
+        assert!(digest_due(DAY + 8 * HOUR, None, Some(0), 0));
+        
+        // 06:00 UTC is 08:00 at UTC+2
+        let shifted = preferences(None, 120);
+        assert!(digest_due(6 * HOUR, Some(&shifted), None, 0));
+    }
+    
+    #[test]
+    fn digest_waits_for_items_queued_after_digest_time() {
+        assert!(!digest_due(20 * HOUR, None, None, 19 * HOUR));
+        assert!(digest_due(DAY + 8 * HOUR, None, None, 19 * HOUR));
+    }
+    
+    #[test]
+    fn digest_is_held_during_quiet_hours() {
+        let quiet = preferences(Some((22 * 60, 9 * 60)), 0);
+        assert!(!digest_due(8 * HOUR, Some(&quiet), None, 0));
+        assert!(digest_due(9 * HOUR, Some(&quiet), None, 0));
+    }
+    
+    #[test]
+    fn queued_items_are_capped() {
+        let mut items = vec![];
+        for _ in 0..MAX_QUEUED_ITEMS_PER_USER + 5 {
+            push_capped(&mut items, item());
+        }
+        assert_eq!(items.len(), MAX_QUEUED_ITEMS_PER_USER);
+    }
+}
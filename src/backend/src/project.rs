@@ -6,6 +6,8 @@ use std::result::Result;
 use crate::types::*;
 use crate::utils;
 use crate::team;
+use crate::activity::{self, ActivityScope};
+use crate::webhook;
 
 // Project management
 pub async fn create_project(name: String, description: String, owner: Owner) -> Result<String, Error> {
@@ -17,20 +19,7 @@ pub async fn create_project(name: String, description: String, owner: Owner) ->
     }
     
     // Validate owner
-    match &owner {
-        Owner::User(owner_principal) => {
-            if owner_principal != &caller_principal {
-                return Err(Error::InsufficientPermissions);
-            }
-        }
-        Owner::Team(team_id) => {
-            let team = team::get_team(team_id.clone()).ok_or(Error::TeamNotFound)?;
-            let user_role = team::get_user_role_in_team(&team, &caller_principal)?;
-            if user_role != Role::Owner && user_role != Role::Manager {
-                return Err(Error::InsufficientPermissions);
-            }
-        }
-    }
+    validate_owner(&owner, &caller_principal)?;
     
     let project_id = utils::generate_id().await;
     let now = time();
@@ -40,15 +29,24 @@ pub async fn create_project(name: String, description: String, owner: Owner) ->
         name,
         description,
         owner,
-        members: vec![],
+        members: vec![ProjectMember {
+            principal: caller_principal,
+            role: Role::Owner,
+            joined_at: now,
+        }],
+        status: ProjectStatus::Active,
+        is_template: false,
         created_at: now,
         updated_at: now,
     };
     
+    let scope = ActivityScope::project(&project);
     crate::PROJECTS.with(|projects| {
         projects.borrow_mut().insert(project_id.clone(), project);
     });
     
+    activity::record(ActivityEntity::Project(project_id.clone()), ActivityAction::Created, scope, vec![]);
+    
     Ok(project_id)
 }
 
@@ -85,14 +83,27 @@ pub async fn update_project(project_id: String, updates: ProjectUpdate) -> Resul
                 return Err(Error::InsufficientPermissions);
             }
             
+            // Archived projects are read-only
+            if project.status == ProjectStatus::Archived {
+                return Err(Error::ProjectArchived);
+            }
+            
+            let mut changes = vec![];
             if let Some(name) = updates.name {
+                activity::diff(&mut changes, "name", Some(project.name.clone()), Some(name.clone()));
                 project.name = name;
             }
             if let Some(description) = updates.description {
+                activity::diff(&mut changes, "description", Some(project.description.clone()), Some(description.clone()));
                 project.description = description;
             }
+            if let Some(is_template) = updates.is_template {
+                activity::diff(&mut changes, "is_template", Some(project.is_template.to_string()), Some(is_template.to_string()));
+                project.is_template = is_template;
+            }
             
             project.updated_at = time();
+            activity::record(ActivityEntity::Project(project_id.clone()), ActivityAction::Updated, ActivityScope::project(project), changes);
             Ok(())
         } else {
             Err(Error::ProjectNotFound)
@@ -103,14 +114,52 @@ pub async fn update_project(project_id: String, updates: ProjectUpdate) -> Resul
 pub async fn delete_project(project_id: String) -> Result<(), Error> {
     let caller_principal = msg_caller();
     
+    // A single mutable borrow covers both the permission check and the removal
+    let scope = crate::PROJECTS.with(|projects| {
+        let mut projects = projects.borrow_mut();
+        let project = projects.get(&project_id).ok_or(Error::ProjectNotFound)?;
+        let user_role = get_user_role_in_project(project, &caller_principal)?;
+        if user_role != Role::Owner {
+            return Err(Error::InsufficientPermissions);
+        }
+        
+        let scope = ActivityScope::project(project);
+        projects.remove(&project_id);
+        Ok(scope)
+    })?;
+    
+    activity::record(ActivityEntity::Project(project_id.clone()), ActivityAction::Deleted, scope, vec![]);
+    webhook::remove_project_webhooks(&project_id);
+    Ok(())
+}
+
+pub async fn transfer_ownership(project_id: String, new_owner: Owner) -> Result<(), Error> {
+    let caller_principal = msg_caller();
+    
     crate::PROJECTS.with(|projects| {
-        if let Some(project) = projects.borrow().get(&project_id) {
+        if let Some(project) = projects.borrow_mut().get_mut(&project_id) {
             let user_role = get_user_role_in_project(project, &caller_principal)?;
             if user_role != Role::Owner {
                 return Err(Error::InsufficientPermissions);
             }
             
-            projects.borrow_mut().remove(&project_id);
+            if project.status == ProjectStatus::Archived {
+                return Err(Error::ProjectArchived);
+            }
+            
+            let changes = vec![activity::change(
+                "owner",
+                Some(activity::owner_value(&project.owner)),
+                Some(activity::owner_value(&new_owner)),
+            )];
+            project.owner = new_owner;
+            project.updated_at = time();
+            activity::record(
+                ActivityEntity::Project(project_id.clone()),
+                ActivityAction::OwnershipTransferred,
+                ActivityScope::project(project),
+                changes,
+            );
             Ok(())
         } else {
             Err(Error::ProjectNotFound)
@@ -118,18 +167,41 @@ pub async fn delete_project(project_id: String) -> Result<(), Error> {
     })
 }
 
-pub async fn transfer_ownership(project_id: String, new_owner: Owner) -> Result<(), Error> {
+pub async fn archive_project(project_id: String) -> Result<(), Error> {
+    set_project_status(project_id, ProjectStatus::Archived)
+}
+
+pub async fn restore_project(project_id: String) -> Result<(), Error> {
+    set_project_status(project_id, ProjectStatus::Active)
+}
+
+fn set_project_status(project_id: String, status: ProjectStatus) -> Result<(), Error> {
     let caller_principal = msg_caller();
     
     crate::PROJECTS.with(|projects| {
         if let Some(project) = projects.borrow_mut().get_mut(&project_id) {
+            // Only Owners can archive or restore a project
             let user_role = get_user_role_in_project(project, &caller_principal)?;
             if user_role != Role::Owner {
                 return Err(Error::InsufficientPermissions);
             }
             
-            project.owner = new_owner;
+            if project.status == status {
+                return Err(Error::InvalidInput("Project already has this status".to_string()));
+            }
+            
+            let action = match status {
+                ProjectStatus::Archived => ActivityAction::Archived,
+                ProjectStatus::Active => ActivityAction::Restored,
+            };
+            let changes = vec![activity::change(
+                "status",
+                Some(activity::status_value(&project.status)),
+                Some(activity::status_value(&status)),
+            )];
+            project.status = status;
             project.updated_at = time();
+            activity::record(ActivityEntity::Project(project_id.clone()), action, ActivityScope::project(project), changes);
             Ok(())
         } else {
             Err(Error::ProjectNotFound)
@@ -137,7 +209,107 @@ pub async fn transfer_ownership(project_id: String, new_owner: Owner) -> Result<
     })
 }
 
-pub fn get_user_projects(principal: Principal) -> Vec<Project> {
+pub async fn clone_project(project_id: String, new_name: String, owner: Owner, options: CloneProjectOptions) -> Result<String, Error> {
+    let caller_principal = msg_caller();
+    
+    // Ensure user exists
+    if !crate::USERS.with(|users| users.borrow().contains_key(&caller_principal)) {
+        return Err(Error::Unauthorized);
+    }
+    
+    if new_name.trim().is_empty() {
+        return Err(Error::InvalidInput("Project name cannot be empty".to_string()));
+    }
+    
+    let source = crate::PROJECTS.with(|projects| projects.borrow().get(&project_id).cloned())
+        .ok_or(Error::ProjectNotFound)?;
+    
+    // Members can clone their projects; team members can also clone the team's templates
+    let is_member = source.members.iter().any(|member| member.principal == caller_principal);
+    let is_team_template = source.is_template && match &source.owner {
+        Owner::Team(team_id) => is_team_member(team_id, &caller_principal),
+        Owner::User(_) => false,
+    };
+    if !is_member && !is_team_template {
+        return Err(Error::InsufficientPermissions);
+    }
+    
+    // Copying members adds people without an invite, so only the source's Owners and Managers can do it
+    if options.include_members {
+        let user_role = get_user_role_in_project(&source, &caller_principal)?;
+        if user_role != Role::Owner && user_role != Role::Manager {
+            return Err(Error::InsufficientPermissions);
+        }
+    }
+    
+    validate_owner(&owner, &caller_principal)?;
+    
+    let new_project_id = utils::generate_id().await;
+    let now = time();
+    
+    // The caller owns the clone; copied members start as Collaborators for the new owner to promote
+    let mut members = vec![ProjectMember {
+        principal: caller_principal,
+        role: Role::Owner,
+        joined_at: now,
+    }];
+    if options.include_members {
+        members.extend(
+            source.members
+                .iter()
+                .filter(|member| member.principal != caller_principal)
+                .map(|member| ProjectMember {
+                    principal: member.principal,
+                    role: Role::Collaborator,
+                    joined_at: now,
+                }),
+        );
+    }
+    
+    let project = Project {
+        id: new_project_id.clone(),
+        name: new_name,
+        description: source.description,
+        owner,
+        members,
+        status: ProjectStatus::Active,
+        is_template: false,
+        created_at: now,
+        updated_at: now,
+    };
+    
+    let copied: Vec<(Principal, ActivityScope)> = project.members
+        .iter()
+        .filter(|member| member.principal != caller_principal)
+        .map(|member| (member.principal, ActivityScope::project(&project)))
+        .collect();
+    let scope = ActivityScope::project(&project);
+    crate::PROJECTS.with(|projects| {
+        projects.borrow_mut().insert(new_project_id.clone(), project);
+    });
+    
+    activity::record(
+        ActivityEntity::Project(new_project_id.clone()),
+        ActivityAction::Created,
+        scope,
+        vec![activity::change("cloned_from", None, Some(project_id))],
+    );
+    for (principal, scope) in copied {
+        activity::record(
+            ActivityEntity::Project(new_project_id.clone()),
+            ActivityAction::MemberAdded,
+            scope,
+            vec![
+                activity::change("member", None, Some(principal.to_text())),
+                activity::change("role", None, Some(activity::role_value(&Role::Collaborator))),
+            ],
+        );
+    }
+    
+    Ok(new_project_id)
+}
+
+pub fn get_user_projects(principal: Principal, include_archived: Option<bool>) -> Vec<Project> {
     let caller_principal = msg_caller();
     
     // Ensure user exists
@@ -157,12 +329,13 @@ pub fn get_user_projects(principal: Principal) -> Vec<Project> {
             .filter(|project| {
                 project.members.iter().any(|member| member.principal == principal)
             })
+            .filter(|project| include_archived.unwrap_or(false) || project.status != ProjectStatus::Archived)
             .cloned()
             .collect()
     })
 }
 
-pub fn get_team_projects(team_id: String) -> Vec<Project> {
+pub fn get_team_projects(team_id: String, include_archived: Option<bool>) -> Vec<Project> {
     let caller_principal = msg_caller();
     
     // Ensure user exists
@@ -171,15 +344,7 @@ pub fn get_team_projects(team_id: String) -> Vec<Project> {
     }
     
     // Check if user is a member of the team
-    let is_team_member = crate::TEAMS.with(|teams| {
-        if let Some(team) = teams.borrow().get(&team_id) {
-            team.members.iter().any(|member| member.principal == caller_principal)
-        } else {
-            false
-        }
-    });
-    
-    if !is_team_member {
+    if !is_team_member(&team_id, &caller_principal) {
         return vec![]; // Return empty for security
     }
     
@@ -190,12 +355,68 @@ pub fn get_team_projects(team_id: String) -> Vec<Project> {
             .filter(|project| {
                 matches!(project.owner, Owner::Team(ref owner_team_id) if owner_team_id == &team_id)
             })
+            .filter(|project| include_archived.unwrap_or(false) || project.status != ProjectStatus::Archived)
+            .cloned()
+            .collect()
+    })
+}
+
+pub fn get_team_project_templates(team_id: String) -> Vec<Project> {
+    let caller_principal = msg_caller();
+    
+    // Ensure user exists
+    if !crate::USERS.with(|users| users.borrow().contains_key(&caller_principal)) {
+        return vec![]; // Return empty if not authenticated
+    }
+    
+    // Only team members can browse the team's template gallery
+    if !is_team_member(&team_id, &caller_principal) {
+        return vec![]; // Return empty for security
+    }
+    
+    crate::PROJECTS.with(|projects| {
+        projects
+            .borrow()
+            .values()
+            .filter(|project| {
+                project.is_template
+                    && project.status != ProjectStatus::Archived
+                    && matches!(project.owner, Owner::Team(ref owner_team_id) if owner_team_id == &team_id)
+            })
             .cloned()
             .collect()
     })
 }
 
 // Helper functions
+fn validate_owner(owner: &Owner, caller_principal: &Principal) -> Result<(), Error> {
+    match owner {
+        Owner::User(owner_principal) => {
+            if owner_principal != caller_principal {
+                return Err(Error::InsufficientPermissions);
+            }
+        }
+        Owner::Team(team_id) => {
+            let team = team::get_team(team_id.clone()).ok_or(Error::TeamNotFound)?;
+            let user_role = team::get_user_role_in_team(&team, caller_principal)?;
+            if user_role != Role::Owner && user_role != Role::Manager {
+                return Err(Error::InsufficientPermissions);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_team_member(team_id: &str, principal: &Principal) -> bool {
+    crate::TEAMS.with(|teams| {
+        if let Some(team) = teams.borrow().get(team_id) {
+            team.members.iter().any(|member| member.principal == *principal)
+        } else {
+            false
+        }
+    })
+}
+
 pub fn get_user_role_in_project(project: &Project, principal: &Principal) -> Result<Role, Error> {
     project.members
         .iter()
@@ -207,6 +428,10 @@ pub fn get_user_role_in_project(project: &Project, principal: &Principal) -> Res
 pub fn add_user_to_project(project_id: &str, principal: &Principal, role: &Role) -> Result<(), Error> {
     crate::PROJECTS.with(|projects| {
         if let Some(project) = projects.borrow_mut().get_mut(project_id) {
+            if project.status == ProjectStatus::Archived {
+                return Err(Error::ProjectArchived);
+            }
+            
             // Check if user is already a member
             if project.members.iter().any(|member| member.principal == *principal) {
                 return Err(Error::AlreadyExists);
@@ -218,6 +443,15 @@ pub fn add_user_to_project(project_id: &str, principal: &Principal, role: &Role)
                 joined_at: time(),
             });
             project.updated_at = time();
+            activity::record(
+                ActivityEntity::Project(project_id.to_string()),
+                ActivityAction::MemberAdded,
+                ActivityScope::project(project),
+                vec![
+                    activity::change("member", None, Some(principal.to_text())),
+                    activity::change("role", None, Some(activity::role_value(role))),
+                ],
+            );
             Ok(())
         } else {
             Err(Error::ProjectNotFound)
@@ -228,8 +462,24 @@ pub fn add_user_to_project(project_id: &str, principal: &Principal, role: &Role)
 pub fn remove_user_from_project(project_id: &str, principal: &Principal) -> Result<(), Error> {
     crate::PROJECTS.with(|projects| {
         if let Some(project) = projects.borrow_mut().get_mut(project_id) {
+            // Archived projects are read-only, including their membership
+            if project.status == ProjectStatus::Archived {
+                return Err(Error::ProjectArchived);
+            }
+            
+            // Nothing to log or notify if the principal was not a member
+            if !project.members.iter().any(|member| member.principal == *principal) {
+                return Err(Error::NotFound);
+            }
+            
             project.members.retain(|member| member.principal != *principal);
             project.updated_at = time();
+            activity::record(
+                ActivityEntity::Project(project_id.to_string()),
+                ActivityAction::MemberRemoved,
+                ActivityScope::project(project),
+                vec![activity::change("member", Some(principal.to_text()), None)],
+            );
             Ok(())
         } else {
             Err(Error::ProjectNotFound)
@@ -5,6 +5,7 @@ use std::result::Result;
 
 use crate::types::*;
 use crate::utils;
+use crate::activity::{self, ActivityScope};
 
 // Team management
 pub async fn create_team(name: String, description: String, is_public: bool) -> Result<String, Error> {
@@ -37,6 +38,8 @@ pub async fn create_team(name: String, description: String, is_public: bool) ->
         teams.borrow_mut().insert(team_id.clone(), team);
     });
     
+    activity::record(ActivityEntity::Team(team_id.clone()), ActivityAction::Created, ActivityScope::team(&team_id), vec![]);
+    
     Ok(team_id)
 }
 
@@ -73,17 +76,22 @@ pub async fn update_team(team_id: String, updates: TeamUpdate) -> Result<(), Err
                 return Err(Error::InsufficientPermissions);
             }
             
+            let mut changes = vec![];
             if let Some(name) = updates.name {
+                activity::diff(&mut changes, "name", Some(team.name.clone()), Some(name.clone()));
                 team.name = name;
             }
             if let Some(description) = updates.description {
+                activity::diff(&mut changes, "description", Some(team.description.clone()), Some(description.clone()));
                 team.description = description;
             }
             if let Some(is_public) = updates.is_public {
+                activity::diff(&mut changes, "is_public", Some(team.is_public.to_string()), Some(is_public.to_string()));
                 team.is_public = is_public;
             }
             
             team.updated_at = time();
+            activity::record(ActivityEntity::Team(team_id.clone()), ActivityAction::Updated, ActivityScope::team(&team_id), changes);
             Ok(())
         } else {
             Err(Error::TeamNotFound)
@@ -94,18 +102,20 @@ pub async fn update_team(team_id: String, updates: TeamUpdate) -> Result<(), Err
 pub async fn delete_team(team_id: String) -> Result<(), Error> {
     let caller_principal = msg_caller();
     
+    // A single mutable borrow covers both the permission check and the removal
     crate::TEAMS.with(|teams| {
-        if let Some(team) = teams.borrow().get(&team_id) {
-            if team.owner_principal != caller_principal {
-                return Err(Error::InsufficientPermissions);
-            }
-            
-            teams.borrow_mut().remove(&team_id);
-            Ok(())
-        } else {
-            Err(Error::TeamNotFound)
+        let mut teams = teams.borrow_mut();
+        let team = teams.get(&team_id).ok_or(Error::TeamNotFound)?;
+        if team.owner_principal != caller_principal {
+            return Err(Error::InsufficientPermissions);
         }
-    })
+        
+        teams.remove(&team_id);
+        Ok(())
+    })?;
+    
+    activity::record(ActivityEntity::Team(team_id.clone()), ActivityAction::Deleted, ActivityScope::team(&team_id), vec![]);
+    Ok(())
 }
 
 pub fn get_user_teams(principal: Principal) -> Vec<Team> {
@@ -167,6 +177,15 @@ pub fn add_user_to_team(team_id: &str, principal: &Principal, role: &Role) -> Re
                 joined_at: time(),
             });
             team.updated_at = time();
+            activity::record(
+                ActivityEntity::Team(team_id.to_string()),
+                ActivityAction::MemberAdded,
+                ActivityScope::team(team_id),
+                vec![
+                    activity::change("member", None, Some(principal.to_text())),
+                    activity::change("role", None, Some(activity::role_value(role))),
+                ],
+            );
             Ok(())
         } else {
             Err(Error::TeamNotFound)
@@ -177,8 +196,19 @@ pub fn add_user_to_team(team_id: &str, principal: &Principal, role: &Role) -> Re
 pub fn remove_user_from_team(team_id: &str, principal: &Principal) -> Result<(), Error> {
     crate::TEAMS.with(|teams| {
         if let Some(team) = teams.borrow_mut().get_mut(team_id) {
+            // Nothing to log or notify if the principal was not a member
+            if !team.members.iter().any(|member| member.principal == *principal) {
+                return Err(Error::NotFound);
+            }
+            
             team.members.retain(|member| member.principal != *principal);
             team.updated_at = time();
+            activity::record(
+                ActivityEntity::Team(team_id.to_string()),
+                ActivityAction::MemberRemoved,
+                ActivityScope::team(team_id),
+                vec![activity::change("member", Some(principal.to_text()), None)],
+            );
             Ok(())
         } else {
             Err(Error::TeamNotFound)
@@ -5,6 +5,11 @@ use serde::Serialize;
 pub type TeamId = String;
 pub type ProjectId = String;
 pub type InviteId = String;
+pub type ActivityId = u64;
+pub type NotificationId = u64;
+pub type WebhookId = String;
+pub type ApiTokenId = String;
+pub type WebhookDeliveryId = u64;
 pub type Timestamp = u64;
 
 // Theme preferences
@@ -14,6 +19,35 @@ pub struct ThemePreferences {
     pub dark_mode: bool,    // true for dark, false for light
 }
 
+// How a user wants to receive a kind of notification
+#[derive(CandidType, Deserialize, Clone, Serialize, PartialEq, Eq)]
+pub enum NotificationDelivery {
+    Immediate,
+    Digest,
+    Off,
+}
+
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct NotificationPreference {
+    pub kind: NotificationKind,
+    pub delivery: NotificationDelivery,
+}
+
+// Quiet hours in minutes after local midnight, may wrap past midnight
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct QuietHours {
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+// Notification preferences
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct NotificationPreferences {
+    pub deliveries: Vec<NotificationPreference>, // kinds not listed are delivered immediately
+    pub quiet_hours: Option<QuietHours>,
+    pub utc_offset_minutes: i16,                 // user's timezone
+}
+
 // User profile information
 #[derive(CandidType, Deserialize, Clone, Serialize)]
 pub struct UserProfile {
@@ -23,6 +57,7 @@ pub struct UserProfile {
     pub avatar_url: Option<String>,
     pub bio: Option<String>,
     pub theme_preferences: Option<ThemePreferences>,
+    pub notification_preferences: Option<NotificationPreferences>,
 }
 
 // Complete user data
@@ -78,6 +113,13 @@ pub struct ProjectMember {
     pub joined_at: Timestamp,
 }
 
+// Project lifecycle status
+#[derive(CandidType, Deserialize, Clone, Serialize, PartialEq, Eq)]
+pub enum ProjectStatus {
+    Active,
+    Archived,
+}
+
 // Project information
 #[derive(CandidType, Deserialize, Clone, Serialize)]
 pub struct Project {
@@ -86,6 +128,8 @@ pub struct Project {
     pub description: String,
     pub owner: Owner,
     pub members: Vec<ProjectMember>,
+    pub status: ProjectStatus,
+    pub is_template: bool,
     pub created_at: Timestamp,
     pub updated_at: Timestamp,
 }
@@ -118,6 +162,181 @@ pub struct Invite {
     pub created_at: Timestamp,
 }
 
+// Entity an activity entry refers to
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub enum ActivityEntity {
+    User(Principal),
+    Team(TeamId),
+    Project(ProjectId),
+    Invite(InviteId),
+    Webhook(WebhookId),
+}
+
+// Kind of mutation recorded in the activity log
+#[derive(CandidType, Deserialize, Clone, Serialize, PartialEq, Eq)]
+pub enum ActivityAction {
+    Created,
+    Updated,
+    Deleted,
+    Archived,
+    Restored,
+    OwnershipTransferred,
+    MemberAdded,
+    MemberRemoved,
+    InviteSent,
+    InviteAccepted,
+    InviteDeclined,
+    InviteCancelled,
+}
+
+// Field-level change, values rendered as text
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+// Activity log entry
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct ActivityEntry {
+    pub id: ActivityId,
+    pub actor: Principal,
+    pub timestamp: Timestamp,
+    pub entity: ActivityEntity,
+    pub action: ActivityAction,
+    pub team_id: Option<TeamId>,
+    pub project_id: Option<ProjectId>,
+    pub changes: Vec<FieldChange>,
+}
+
+// Page of activity entries, newest first
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct ActivityPage {
+    pub entries: Vec<ActivityEntry>,
+    pub total: u64,
+}
+
+// Activity log retention settings
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct ActivityRetention {
+    pub max_entries_per_scope: u64, // per project, team or user
+    pub max_total_entries: u64,     // across the whole canister
+}
+
+// Kind of event a notification reports
+#[derive(CandidType, Deserialize, Clone, Serialize, PartialEq, Eq)]
+pub enum NotificationKind {
+    InviteReceived,
+    InviteAccepted,
+    InviteDeclined,
+    MemberRemoved,
+    DailyDigest,
+}
+
+// Event rolled up into a daily digest
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct DigestItem {
+    pub kind: NotificationKind,
+    pub actor: Principal,
+    pub target: InviteTarget,
+    pub invite_id: Option<InviteId>,
+    pub created_at: Timestamp,
+}
+
+// Notification in a user's inbox
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct Notification {
+    pub id: NotificationId,
+    pub kind: NotificationKind,
+    pub actor: Principal,
+    pub target: Option<InviteTarget>,
+    pub invite_id: Option<InviteId>,
+    pub digest: Vec<DigestItem>,
+    pub read: bool,
+    pub created_at: Timestamp,
+}
+
+// Page of notifications, newest first
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct NotificationPage {
+    pub notifications: Vec<Notification>,
+    pub total: u64,
+}
+
+// Outbound webhook registered on a project
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub project_id: ProjectId,
+    pub url: String,
+    pub event_filter: Vec<ActivityAction>, // empty means every action
+    pub secret: String,                    // HMAC-SHA256 signing key
+    pub enabled: bool,
+    pub consecutive_failures: u32,
+    pub created_by: Principal,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+// Webhook delivery status
+#[derive(CandidType, Deserialize, Clone, Serialize, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+// Single event delivery to a webhook, including retries
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct WebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub webhook_id: WebhookId,
+    pub activity_id: ActivityId,
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+// Personal API token for the HTTP API, the secret itself is only returned once
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct ApiToken {
+    pub id: ApiTokenId,
+    pub name: String,
+    pub owner: Principal,
+    pub created_at: Timestamp,
+}
+
+// HTTP gateway request and response
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub upgrade: Option<bool>,
+}
+
+// Chunk of a serialized project archive
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct ExportChunk {
+    pub data: Vec<u8>,
+    pub offset: u64,
+    pub total_size: u64,
+    pub snapshot: Timestamp, // project updated_at the archive was built from
+}
+
 // Update types for partial updates
 #[derive(CandidType, Deserialize, Clone, Serialize)]
 pub struct UserProfileUpdate {
@@ -127,6 +346,7 @@ pub struct UserProfileUpdate {
     pub avatar_url: Option<Option<String>>,
     pub bio: Option<Option<String>>,
     pub theme_preferences: Option<Option<ThemePreferences>>,
+    pub notification_preferences: Option<Option<NotificationPreferences>>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Serialize)]
@@ -140,6 +360,20 @@ pub struct TeamUpdate {
 pub struct ProjectUpdate {
     pub name: Option<String>,
     pub description: Option<String>,
+    pub is_template: Option<bool>,
+}
+
+// Options for cloning a project
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct CloneProjectOptions {
+    pub include_members: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Serialize)]
+pub struct WebhookUpdate {
+    pub url: Option<String>,
+    pub event_filter: Option<Vec<ActivityAction>>,
+    pub enabled: Option<bool>,
 }
 
 // Error types
@@ -155,6 +389,7 @@ pub enum Error {
     UserNotFound,
     TeamNotFound,
     ProjectNotFound,
+    ProjectArchived,
     InternalError(String),
 }
 
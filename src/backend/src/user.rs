@@ -4,6 +4,8 @@ use ic_cdk::api::msg_caller;
 use std::result::Result;
 
 use crate::types::*;
+use crate::activity::{self, ActivityScope};
+use crate::notification;
 
 // User management
 pub async fn create_user(profile: UserProfile) -> Result<Principal, Error> {
@@ -27,6 +29,10 @@ pub async fn create_user(profile: UserProfile) -> Result<Principal, Error> {
         return Err(Error::InvalidInput("Username too long (max 12 characters)".to_string()));
     }
     
+    if let Some(notification_preferences) = &profile.notification_preferences {
+        notification::validate_preferences(notification_preferences)?;
+    }
+    
     // Check if username is already taken by another user
     let username_taken = crate::USERS.with(|users| {
         users.borrow().values().any(|user| user.profile.username == profile.username)
@@ -49,6 +55,8 @@ pub async fn create_user(profile: UserProfile) -> Result<Principal, Error> {
         users.borrow_mut().insert(caller_principal, user);
     });
     
+    activity::record(ActivityEntity::User(caller_principal), ActivityAction::Created, ActivityScope::none(), vec![]);
+    
     Ok(caller_principal)
 }
 
@@ -71,27 +79,53 @@ pub async fn update_profile(principal: Principal, profile_update: UserProfileUpd
         return Err(Error::Unauthorized);
     }
     
+    if let Some(Some(notification_preferences)) = &profile_update.notification_preferences {
+        notification::validate_preferences(notification_preferences)?;
+    }
+    
     crate::USERS.with(|users| {
         if let Some(user) = users.borrow_mut().get_mut(&principal) {
+            let mut changes = vec![];
             if let Some(name) = profile_update.name {
+                activity::diff(&mut changes, "name", Some(user.profile.name.clone()), Some(name.clone()));
                 user.profile.name = name;
             }
             if let Some(username) = profile_update.username {
+                activity::diff(&mut changes, "username", Some(user.profile.username.clone()), Some(username.clone()));
                 user.profile.username = username;
             }
             if let Some(email) = profile_update.email {
+                activity::diff(&mut changes, "email", user.profile.email.clone(), email.clone());
                 user.profile.email = email;
             }
             if let Some(avatar_url) = profile_update.avatar_url {
+                activity::diff(&mut changes, "avatar_url", user.profile.avatar_url.clone(), avatar_url.clone());
                 user.profile.avatar_url = avatar_url;
             }
             if let Some(bio) = profile_update.bio {
+                activity::diff(&mut changes, "bio", user.profile.bio.clone(), bio.clone());
                 user.profile.bio = bio;
             }
             if let Some(theme_preferences) = profile_update.theme_preferences {
+                activity::diff(
+                    &mut changes,
+                    "theme_preferences",
+                    user.profile.theme_preferences.as_ref().map(activity::theme_value),
+                    theme_preferences.as_ref().map(activity::theme_value),
+                );
                 user.profile.theme_preferences = theme_preferences;
             }
+            if let Some(notification_preferences) = profile_update.notification_preferences {
+                activity::diff(
+                    &mut changes,
+                    "notification_preferences",
+                    user.profile.notification_preferences.as_ref().map(activity::notification_preferences_value),
+                    notification_preferences.as_ref().map(activity::notification_preferences_value),
+                );
+                user.profile.notification_preferences = notification_preferences;
+            }
             user.updated_at = time();
+            activity::record(ActivityEntity::User(principal), ActivityAction::Updated, ActivityScope::none(), changes);
             Ok(())
         } else {
             Err(Error::UserNotFound)
@@ -104,8 +138,40 @@ pub async fn update_theme_preferences(theme_preferences: ThemePreferences) -> Re
     
     crate::USERS.with(|users| {
         if let Some(user) = users.borrow_mut().get_mut(&caller_principal) {
+            let mut changes = vec![];
+            activity::diff(
+                &mut changes,
+                "theme_preferences",
+                user.profile.theme_preferences.as_ref().map(activity::theme_value),
+                Some(activity::theme_value(&theme_preferences)),
+            );
             user.profile.theme_preferences = Some(theme_preferences);
             user.updated_at = time();
+            activity::record(ActivityEntity::User(caller_principal), ActivityAction::Updated, ActivityScope::none(), changes);
+            Ok(())
+        } else {
+            Err(Error::UserNotFound)
+        }
+    })
+}
+
+pub async fn update_notification_preferences(notification_preferences: NotificationPreferences) -> Result<(), Error> {
+    let caller_principal = msg_caller();
+    
+    notification::validate_preferences(&notification_preferences)?;
+    
+    crate::USERS.with(|users| {
+        if let Some(user) = users.borrow_mut().get_mut(&caller_principal) {
+            let mut changes = vec![];
+            activity::diff(
+                &mut changes,
+                "notification_preferences",
+                user.profile.notification_preferences.as_ref().map(activity::notification_preferences_value),
+                Some(activity::notification_preferences_value(&notification_preferences)),
+            );
+            user.profile.notification_preferences = Some(notification_preferences);
+            user.updated_at = time();
+            activity::record(ActivityEntity::User(caller_principal), ActivityAction::Updated, ActivityScope::none(), changes);
             Ok(())
         } else {
             Err(Error::UserNotFound)
@@ -132,8 +198,11 @@ pub async fn update_username(principal: Principal, username: String) -> Result<(
     
     crate::USERS.with(|users| {
         if let Some(user) = users.borrow_mut().get_mut(&principal) {
+            let mut changes = vec![];
+            activity::diff(&mut changes, "username", Some(user.profile.username.clone()), Some(username.clone()));
             user.profile.username = username;
             user.updated_at = time();
+            activity::record(ActivityEntity::User(principal), ActivityAction::Updated, ActivityScope::none(), changes);
             Ok(())
         } else {
             Err(Error::UserNotFound)
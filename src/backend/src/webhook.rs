@@ -0,0 +1,553 @@
+use candid::Principal;
+use hmac::{Hmac, Mac};
+use ic_cdk::api::time;
+use ic_cdk::api::msg_caller;
+use ic_cdk::management_canister::{self, HttpHeader, HttpMethod, HttpRequestArgs, HttpRequestResult, TransformArgs};
+use serde::Serialize;
+use sha2::Sha256;
+use std::result::Result;
+use std::time::Duration;
+
+use crate::types::*;
+use crate::utils;
+use crate::project;
+use crate::activity::{self, ActivityScope};
+
+const MAX_WEBHOOKS_PER_PROJECT: usize = 10;
+const MAX_DELIVERIES_PER_WEBHOOK: usize = 100;
+const MAX_ATTEMPTS: u32 = 5;
+const BASE_RETRY_DELAY_SECS: u64 = 30;
+const MAX_CONSECUTIVE_FAILURES: u32 = 10; // failed attempts before a webhook is disabled
+const MAX_RESPONSE_BYTES: u64 = 2_048;
+const TRANSFORM_METHOD: &str = "transform_webhook_response";
+
+// Body sent to webhook endpoints
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    webhook_id: &'a str,
+    project_id: &'a str,
+    event: &'a ActivityEntry,
+}
+
+// Webhook management
+pub async fn register_webhook(project_id: String, url: String, event_filter: Vec<ActivityAction>) -> Result<WebhookId, Error> {
+    let caller_principal = msg_caller();
+    
+    validate_url(&url)?;
+    ensure_can_register(&project_id, &caller_principal)?;
+    
+    let webhook_id = utils::generate_id().await;
+    let secret = utils::generate_id().await;
+    
+    // Other calls may have run during the awaits, so check again before inserting
+    ensure_can_register(&project_id, &caller_principal)?;
+    let now = time();
+    
+    // The secret is never logged
+    let changes = vec![
+        activity::change("url", None, Some(url.clone())),
+        activity::change("event_filter", None, Some(activity::event_filter_value(&event_filter))),
+    ];
+    let scope = project_scope(&project_id);
+    
+    let webhook = Webhook {
+        id: webhook_id.clone(),
+        project_id,
+        url,
+        event_filter,
+        secret,
+        enabled: true,
+        consecutive_failures: 0,
+        created_by: caller_principal,
+        created_at: now,
+        updated_at: now,
+    };
+    
+    crate::WEBHOOKS.with(|webhooks| {
+        webhooks.borrow_mut().insert(webhook_id.clone(), webhook);
+    });
+    
+    activity::record(ActivityEntity::Webhook(webhook_id.clone()), ActivityAction::Created, scope, changes);
+    
+    Ok(webhook_id)
+}
+
+pub async fn update_webhook(webhook_id: String, updates: WebhookUpdate) -> Result<(), Error> {
+    let caller_principal = msg_caller();
+    
+    let project_id = webhook_project(&webhook_id)?;
+    ensure_can_manage(&project_id, &caller_principal)?;
+    
+    if let Some(url) = &updates.url {
+        validate_url(url)?;
+    }
+    
+    let (disabled, changes) = crate::WEBHOOKS.with(|webhooks| {
+        if let Some(webhook) = webhooks.borrow_mut().get_mut(&webhook_id) {
+            let was_enabled = webhook.enabled;
+            let mut changes = Vec::new();
+            if let Some(url) = updates.url {
+                activity::diff(&mut changes, "url", Some(webhook.url.clone()), Some(url.clone()));
+                webhook.url = url;
+            }
+            if let Some(event_filter) = updates.event_filter {
+                activity::diff(
+                    &mut changes,
+                    "event_filter",
+                    Some(activity::event_filter_value(&webhook.event_filter)),
+                    Some(activity::event_filter_value(&event_filter)),
+                );
+                webhook.event_filter = event_filter;
+            }
+            if let Some(enabled) = updates.enabled {
+                // Re-enabling starts the failure count over
+                if enabled && !webhook.enabled {
+                    webhook.consecutive_failures = 0;
+                }
+                activity::diff(&mut changes, "enabled", Some(webhook.enabled.to_string()), Some(enabled.to_string()));
+                webhook.enabled = enabled;
+            }
+            
+            webhook.updated_at = time();
+            Ok((was_enabled && !webhook.enabled, changes))
+        } else {
+            Err(Error::NotFound)
+        }
+    })?;
+    
+    if disabled {
+        fail_pending(&webhook_id, "Webhook was disabled");
+    }
+    
+    activity::record(ActivityEntity::Webhook(webhook_id), ActivityAction::Updated, project_scope(&project_id), changes);
+    Ok(())
+}
+
+pub async fn delete_webhook(webhook_id: String) -> Result<(), Error> {
+    let caller_principal = msg_caller();
+    
+    let project_id = webhook_project(&webhook_id)?;
+    ensure_can_manage(&project_id, &caller_principal)?;
+    
+    let removed = crate::WEBHOOKS.with(|webhooks| webhooks.borrow_mut().remove(&webhook_id));
+    crate::WEBHOOK_DELIVERIES.with(|deliveries| deliveries.borrow_mut().remove(&webhook_id));
+    
+    let changes = removed
+        .map(|webhook| vec![activity::change("url", Some(webhook.url), None)])
+        .unwrap_or_default();
+    activity::record(ActivityEntity::Webhook(webhook_id), ActivityAction::Deleted, project_scope(&project_id), changes);
+    Ok(())
+}
+
+pub fn get_project_webhooks(project_id: String) -> Result<Vec<Webhook>, Error> {
+    let caller_principal = msg_caller();
+    
+    ensure_can_view(&project_id, &caller_principal)?;
+    
+    Ok(crate::WEBHOOKS.with(|webhooks| {
+        webhooks
+            .borrow()
+            .values()
+            .filter(|webhook| webhook.project_id == project_id)
+            .cloned()
+            .collect()
+    }))
+}
+
+pub fn get_webhook_deliveries(webhook_id: String) -> Result<Vec<WebhookDelivery>, Error> {
+    let caller_principal = msg_caller();
+    
+    let project_id = webhook_project(&webhook_id)?;
+    ensure_can_view(&project_id, &caller_principal)?;
+    
+    Ok(crate::WEBHOOK_DELIVERIES.with(|deliveries| {
+        deliveries
+            .borrow()
+            .get(&webhook_id)
+            .map(|log| log.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }))
+}
+
+// Webhooks still delivering, such as the project's Deleted event, are released once they finish
+pub fn remove_project_webhooks(project_id: &str) {
+    let webhook_ids: Vec<WebhookId> = crate::WEBHOOKS.with(|webhooks| {
+        webhooks
+            .borrow()
+            .values()
+            .filter(|webhook| webhook.project_id == project_id)
+            .map(|webhook| webhook.id.clone())
+            .collect()
+    });
+    
+    for webhook_id in webhook_ids {
+        release_if_orphaned(&webhook_id);
+    }
+}
+
+// Dispatch
+pub fn enqueue(entry: &ActivityEntry) {
+    let Some(project_id) = &entry.project_id else {
+        return;
+    };
+    
+    let targets: Vec<WebhookId> = crate::WEBHOOKS.with(|webhooks| {
+        webhooks
+            .borrow()
+            .values()
+            .filter(|webhook| {
+                webhook.enabled
+                    && webhook.project_id == *project_id
+                    && (webhook.event_filter.is_empty() || webhook.event_filter.contains(&entry.action))
+            })
+            .map(|webhook| webhook.id.clone())
+            .collect()
+    });
+    
+    for webhook_id in targets {
+        let payload = WebhookPayload {
+            webhook_id: &webhook_id,
+            project_id,
+            event: entry,
+        };
+        let payload = match serde_json::to_string(&payload) {
+            Ok(payload) => payload,
+            Err(_) => continue,
+        };
+        
+        let delivery_id = crate::NEXT_WEBHOOK_DELIVERY_ID.with(|next_id| {
+            let mut next_id = next_id.borrow_mut();
+            let id = *next_id;
+            *next_id += 1;
+            id
+        });
+        
+        let now = time();
+        let delivery = WebhookDelivery {
+            id: delivery_id,
+            webhook_id: webhook_id.clone(),
+            activity_id: entry.id,
+            payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            last_status_code: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        
+        crate::WEBHOOK_DELIVERIES.with(|deliveries| {
+            let mut deliveries = deliveries.borrow_mut();
+            let log = deliveries.entry(webhook_id.clone()).or_default();
+            log.push_back(delivery);
+            while log.len() > MAX_DELIVERIES_PER_WEBHOOK {
+                log.pop_front();
+            }
+        });
+        
+        schedule(webhook_id, delivery_id, Duration::ZERO);
+    }
+}
+
+fn schedule(webhook_id: WebhookId, delivery_id: WebhookDeliveryId, delay: Duration) {
+    ic_cdk_timers::set_timer(delay, move || ic_cdk::futures::spawn(deliver(webhook_id, delivery_id)));
+}
+
+async fn deliver(webhook_id: WebhookId, delivery_id: WebhookDeliveryId) {
+    let Some((url, secret)) = crate::WEBHOOKS.with(|webhooks| {
+        webhooks
+            .borrow()
+            .get(&webhook_id)
+            .filter(|webhook| webhook.enabled)
+            .map(|webhook| (webhook.url.clone(), webhook.secret.clone()))
+    }) else {
+        release_if_orphaned(&webhook_id);
+        return;
+    };
+    
+    let Some(payload) = crate::WEBHOOK_DELIVERIES.with(|deliveries| {
+        deliveries
+            .borrow()
+            .get(&webhook_id)
+            .and_then(|log| log.iter().find(|delivery| delivery.id == delivery_id))
+            .filter(|delivery| delivery.status == WebhookDeliveryStatus::Pending)
+            .map(|delivery| delivery.payload.clone())
+    }) else {
+        release_if_orphaned(&webhook_id);
+        return;
+    };
+    
+    let request = HttpRequestArgs {
+        url,
+        max_response_bytes: Some(MAX_RESPONSE_BYTES),
+        method: HttpMethod::POST,
+        headers: vec![
+            HttpHeader { name: "Content-Type".to_string(), value: "application/json".to_string() },
+            HttpHeader { name: "X-Kanban-Delivery".to_string(), value: delivery_id.to_string() },
+            HttpHeader { name: "X-Kanban-Signature".to_string(), value: format!("sha256={}", sign(&secret, payload.as_bytes())) },
+        ],
+        body: Some(payload.into_bytes()),
+        transform: Some(management_canister::transform_context_from_query(TRANSFORM_METHOD.to_string(), vec![])),
+    };
+    
+    let outcome = match management_canister::http_request(&request).await {
+        Ok(response) => {
+            let status = u16::try_from(&response.status.0).unwrap_or(0);
+            if (200..300).contains(&status) {
+                Ok(status)
+            } else {
+                Err((Some(status), format!("Endpoint responded with status {}", status)))
+            }
+        }
+        Err(err) => Err((None, err.to_string())),
+    };
+    
+    record_attempt(&webhook_id, delivery_id, outcome);
+}
+
+fn record_attempt(webhook_id: &str, delivery_id: WebhookDeliveryId, outcome: Result<u16, (Option<u16>, String)>) {
+    let succeeded = outcome.is_ok();
+    
+    let attempts = crate::WEBHOOK_DELIVERIES.with(|deliveries| {
+        let mut deliveries = deliveries.borrow_mut();
+        let delivery = deliveries
+            .get_mut(webhook_id)
+            .and_then(|log| log.iter_mut().find(|delivery| delivery.id == delivery_id))?;
+        
+        delivery.attempts += 1;
+        delivery.updated_at = time();
+        match outcome {
+            Ok(status) => {
+                delivery.status = WebhookDeliveryStatus::Delivered;
+                delivery.last_status_code = Some(status);
+                delivery.last_error = None;
+            }
+            Err((status, error)) => {
+                delivery.last_status_code = status;
+                delivery.last_error = Some(error);
+                if delivery.attempts >= MAX_ATTEMPTS {
+                    delivery.status = WebhookDeliveryStatus::Failed;
+                }
+            }
+        }
+        Some(delivery.attempts)
+    });
+    
+    let (still_enabled, auto_disabled) = crate::WEBHOOKS.with(|webhooks| {
+        let mut webhooks = webhooks.borrow_mut();
+        let Some(webhook) = webhooks.get_mut(webhook_id) else {
+            return (false, false);
+        };
+        let was_enabled = webhook.enabled;
+        if succeeded {
+            webhook.consecutive_failures = 0;
+        } else {
+            webhook.consecutive_failures += 1;
+            if webhook.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                webhook.enabled = false;
+                webhook.updated_at = time();
+            }
+        }
+        (webhook.enabled, was_enabled && !webhook.enabled)
+    });
+    
+    if auto_disabled {
+        fail_pending(webhook_id, "Webhook was disabled after repeated failures");
+    }
+    
+    // Retry with exponential backoff while attempts remain
+    if let Some(attempts) = attempts {
+        if !succeeded && still_enabled && attempts < MAX_ATTEMPTS {
+            schedule(webhook_id.to_string(), delivery_id, retry_delay(attempts));
+            return;
+        }
+    }
+    
+    release_if_orphaned(webhook_id);
+}
+
+// Disabled webhooks never deliver, so anything still queued is closed out
+fn fail_pending(webhook_id: &str, reason: &str) {
+    crate::WEBHOOK_DELIVERIES.with(|deliveries| {
+        let mut deliveries = deliveries.borrow_mut();
+        let Some(log) = deliveries.get_mut(webhook_id) else {
+            return;
+        };
+        let now = time();
+        for delivery in log.iter_mut().filter(|delivery| delivery.status == WebhookDeliveryStatus::Pending) {
+            delivery.status = WebhookDeliveryStatus::Failed;
+            delivery.last_error = Some(reason.to_string());
+            delivery.updated_at = now;
+        }
+    });
+}
+
+// Drops a webhook of a deleted project once nothing is left to deliver
+fn release_if_orphaned(webhook_id: &str) {
+    let Some(project_id) = crate::WEBHOOKS.with(|webhooks| {
+        webhooks.borrow().get(webhook_id).map(|webhook| webhook.project_id.clone())
+    }) else {
+        return;
+    };
+    if crate::PROJECTS.with(|projects| projects.borrow().contains_key(&project_id)) {
+        return;
+    }
+    
+    let enabled = crate::WEBHOOKS.with(|webhooks| webhooks.borrow().get(webhook_id).is_some_and(|webhook| webhook.enabled));
+    let has_pending = crate::WEBHOOK_DELIVERIES.with(|deliveries| {
+        deliveries
+            .borrow()
+            .get(webhook_id)
+            .is_some_and(|log| log.iter().any(|delivery| delivery.status == WebhookDeliveryStatus::Pending))
+    });
+    if enabled && has_pending {
+        return;
+    }
+    
+    crate::WEBHOOKS.with(|webhooks| webhooks.borrow_mut().remove(webhook_id));
+    crate::WEBHOOK_DELIVERIES.with(|deliveries| deliveries.borrow_mut().remove(webhook_id));
+}
+
+// Strips headers and body so all replicas agree on the outcall response
+pub fn transform_response(args: TransformArgs) -> HttpRequestResult {
+    HttpRequestResult {
+        status: args.response.status,
+        headers: vec![],
+        body: vec![],
+    }
+}
+
+// Helper functions
+fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// Exponential backoff: 30s, 60s, 120s, ...
+fn retry_delay(attempts: u32) -> Duration {
+    Duration::from_secs(BASE_RETRY_DELAY_SECS << attempts.saturating_sub(1))
+}
+
+fn validate_url(url: &str) -> Result<(), Error> {
+    if url.starts_with("https://") {
+        return Ok(());
+    }
+    
+    // Plain HTTP is only allowed for local stand-ins during development
+    let is_local = url
+        .strip_prefix("http://")
+        .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default())
+        .is_some_and(|authority| {
+            let (host, port) = match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            };
+            let valid_port = port.is_none_or(|port| port.parse::<u16>().is_ok());
+            (host == "localhost" || host == "127.0.0.1") && valid_port
+        });
+    if !is_local {
+        return Err(Error::InvalidInput("Webhook URL must use https".to_string()));
+    }
+    Ok(())
+}
+
+fn project_scope(project_id: &str) -> ActivityScope {
+    crate::PROJECTS.with(|projects| {
+        projects
+            .borrow()
+            .get(project_id)
+            .map(ActivityScope::project)
+            .unwrap_or(ActivityScope { team_id: None, project_id: Some(project_id.to_string()) })
+    })
+}
+
+fn ensure_can_register(project_id: &str, principal: &Principal) -> Result<(), Error> {
+    ensure_can_manage(project_id, principal)?;
+    
+    let webhook_count = crate::WEBHOOKS.with(|webhooks| {
+        webhooks.borrow().values().filter(|webhook| webhook.project_id == project_id).count()
+    });
+    if webhook_count >= MAX_WEBHOOKS_PER_PROJECT {
+        return Err(Error::InvalidInput("Too many webhooks for this project".to_string()));
+    }
+    Ok(())
+}
+
+fn webhook_project(webhook_id: &str) -> Result<ProjectId, Error> {
+    crate::WEBHOOKS.with(|webhooks| {
+        webhooks
+            .borrow()
+            .get(webhook_id)
+            .map(|webhook| webhook.project_id.clone())
+            .ok_or(Error::NotFound)
+    })
+}
+
+fn ensure_can_view(project_id: &str, principal: &Principal) -> Result<(), Error> {
+    crate::PROJECTS.with(|projects| {
+        let projects = projects.borrow();
+        let project = projects.get(project_id).ok_or(Error::ProjectNotFound)?;
+        let user_role = project::get_user_role_in_project(project, principal)?;
+        if user_role != Role::Owner && user_role != Role::Manager {
+            return Err(Error::InsufficientPermissions);
+        }
+        Ok(())
+    })
+}
+
+fn ensure_can_manage(project_id: &str, principal: &Principal) -> Result<(), Error> {
+    ensure_can_view(project_id, principal)?;
+    
+    // Archived projects are read-only
+    let is_archived = crate::PROJECTS.with(|projects| {
+        projects
+            .borrow()
+            .get(project_id)
+            .is_some_and(|project| project.status == ProjectStatus::Archived)
+    });
+    if is_archived {
+        return Err(Error::ProjectArchived);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn https_urls_are_accepted() {
+        assert!(validate_url("https://example.com/hooks").is_ok());
+        assert!(validate_url("ftp://example.com").is_err());
+        assert!(validate_url("http://example.com").is_err());
+    }
+    
+    #[test]
+    fn plain_http_is_limited_to_local_hosts() {
+        assert!(validate_url("http://localhost").is_ok());
+        assert!(validate_url("http://localhost:8080/hook").is_ok());
+        assert!(validate_url("http://127.0.0.1:3000?x=1").is_ok());
+        assert!(validate_url("http://localhost.evil.example/hook").is_err());
+        assert!(validate_url("http://127.0.0.1.nip.io").is_err());
+        assert!(validate_url("http://localhost@evil.example").is_err());
+        assert!(validate_url("http://localhost:80@evil.example").is_err());
+        assert!(validate_url("http://localhost:notaport").is_err());
+    }
+    
+    #[test]
+    fn signature_is_hmac_sha256_hex() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+    
+    #[test]
+    fn retry_delay_doubles_per_attempt() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+    }
+}
@@ -2,7 +2,57 @@ import type { Principal } from '@dfinity/principal';
 import type { ActorMethod } from '@dfinity/agent';
 import type { IDL } from '@dfinity/candid';
 
+export type ActivityAction = { 'MemberAdded' : null } |
+  { 'InviteCancelled' : null } |
+  { 'Updated' : null } |
+  { 'InviteAccepted' : null } |
+  { 'InviteDeclined' : null } |
+  { 'InviteSent' : null } |
+  { 'Archived' : null } |
+  { 'Restored' : null } |
+  { 'MemberRemoved' : null } |
+  { 'Created' : null } |
+  { 'Deleted' : null } |
+  { 'OwnershipTransferred' : null };
+export type ActivityEntity = { 'Webhook' : string } |
+  { 'Team' : string } |
+  { 'User' : Principal } |
+  { 'Project' : string } |
+  { 'Invite' : string };
+export interface ActivityEntry {
+  'id' : bigint,
+  'entity' : ActivityEntity,
+  'action' : ActivityAction,
+  'actor' : Principal,
+  'team_id' : [] | [string],
+  'timestamp' : bigint,
+  'project_id' : [] | [string],
+  'changes' : Array<FieldChange>,
+}
+export interface ActivityPage {
+  'total' : bigint,
+  'entries' : Array<ActivityEntry>,
+}
+export interface ActivityRetention {
+  'max_entries_per_scope' : bigint,
+  'max_total_entries' : bigint,
+}
+export interface ApiToken {
+  'id' : string,
+  'owner' : Principal,
+  'name' : string,
+  'created_at' : bigint,
+}
+export interface CloneProjectOptions { 'include_members' : boolean }
+export interface DigestItem {
+  'actor' : Principal,
+  'kind' : NotificationKind,
+  'invite_id' : [] | [string],
+  'created_at' : bigint,
+  'target' : InviteTarget,
+}
 export type Error = { 'InvalidInput' : string } |
+  { 'ProjectArchived' : null } |
   { 'NotFound' : null } |
   { 'InviteNotFound' : null } |
   { 'InviteExpired' : null } |
@@ -13,6 +63,29 @@ export type Error = { 'InvalidInput' : string } |
   { 'InternalError' : string } |
   { 'ProjectNotFound' : null } |
   { 'UserNotFound' : null };
+export interface ExportChunk {
+  'snapshot' : bigint,
+  'data' : Uint8Array | number[],
+  'offset' : bigint,
+  'total_size' : bigint,
+}
+export interface FieldChange {
+  'field' : string,
+  'after' : [] | [string],
+  'before' : [] | [string],
+}
+export interface HttpRequest {
+  'url' : string,
+  'method' : string,
+  'body' : Uint8Array | number[],
+  'headers' : Array<[string, string]>,
+}
+export interface HttpResponse {
+  'body' : Uint8Array | number[],
+  'headers' : Array<[string, string]>,
+  'upgrade' : [] | [boolean],
+  'status_code' : number,
+}
 export interface Invite {
   'id' : string,
   'status' : InviteStatus,
@@ -28,12 +101,45 @@ export type InviteStatus = { 'Accepted' : null } |
   { 'Pending' : null };
 export type InviteTarget = { 'Team' : string } |
   { 'Project' : string };
+export interface Notification {
+  'id' : bigint,
+  'actor' : Principal,
+  'kind' : NotificationKind,
+  'read' : boolean,
+  'invite_id' : [] | [string],
+  'created_at' : bigint,
+  'target' : [] | [InviteTarget],
+  'digest' : Array<DigestItem>,
+}
+export type NotificationDelivery = { 'Off' : null } |
+  { 'Immediate' : null } |
+  { 'Digest' : null };
+export type NotificationKind = { 'DailyDigest' : null } |
+  { 'InviteAccepted' : null } |
+  { 'InviteDeclined' : null } |
+  { 'InviteReceived' : null } |
+  { 'MemberRemoved' : null };
+export interface NotificationPage {
+  'total' : bigint,
+  'notifications' : Array<Notification>,
+}
+export interface NotificationPreference {
+  'kind' : NotificationKind,
+  'delivery' : NotificationDelivery,
+}
+export interface NotificationPreferences {
+  'deliveries' : Array<NotificationPreference>,
+  'utc_offset_minutes' : number,
+  'quiet_hours' : [] | [QuietHours],
+}
 export type Owner = { 'Team' : string } |
   { 'User' : Principal };
 export interface Project {
   'id' : string,
+  'status' : ProjectStatus,
   'updated_at' : bigint,
   'members' : Array<TeamMember>,
+  'is_template' : boolean,
   'owner' : Owner,
   'name' : string,
   'description' : string,
@@ -44,16 +150,28 @@ export interface ProjectMember {
   'role' : Role,
   'joined_at' : bigint,
 }
+export type ProjectStatus = { 'Active' : null } |
+  { 'Archived' : null };
 export interface ProjectUpdate {
+  'is_template' : [] | [boolean],
   'name' : [] | [string],
   'description' : [] | [string],
 }
+export interface QuietHours { 'start_minute' : number, 'end_minute' : number }
 export type Result = { 'Ok' : null } |
   { 'Err' : Error };
 export type Result_1 = { 'Ok' : string } |
   { 'Err' : Error };
 export type Result_2 = { 'Ok' : Principal } |
   { 'Err' : Error };
+export type Result_3 = { 'Ok' : ExportChunk } |
+  { 'Err' : Error };
+export type Result_4 = { 'Ok' : ActivityPage } |
+  { 'Err' : Error };
+export type Result_5 = { 'Ok' : Array<Webhook> } |
+  { 'Err' : Error };
+export type Result_6 = { 'Ok' : Array<WebhookDelivery> } |
+  { 'Err' : Error };
 export type Role = { 'Collaborator' : null } |
   { 'Owner' : null } |
   { 'Manager' : null };
@@ -86,6 +204,7 @@ export interface User {
 }
 export interface UserProfile {
   'bio' : [] | [string],
+  'notification_preferences' : [] | [NotificationPreferences],
   'username' : string,
   'avatar_url' : [] | [string],
   'name' : string,
@@ -94,40 +213,110 @@ export interface UserProfile {
 }
 export interface UserProfileUpdate {
   'bio' : [] | [[] | [string]],
+  'notification_preferences' : [] | [[] | [NotificationPreferences]],
   'username' : [] | [string],
   'avatar_url' : [] | [[] | [string]],
   'name' : [] | [string],
   'theme_preferences' : [] | [[] | [ThemePreferences]],
   'email' : [] | [[] | [string]],
 }
+export interface Webhook {
+  'id' : string,
+  'url' : string,
+  'updated_at' : bigint,
+  'secret' : string,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'enabled' : boolean,
+  'event_filter' : Array<ActivityAction>,
+  'project_id' : string,
+  'consecutive_failures' : number,
+}
+export interface WebhookDelivery {
+  'id' : bigint,
+  'last_error' : [] | [string],
+  'status' : WebhookDeliveryStatus,
+  'updated_at' : bigint,
+  'attempts' : number,
+  'created_at' : bigint,
+  'activity_id' : bigint,
+  'last_status_code' : [] | [number],
+  'payload' : string,
+  'webhook_id' : string,
+}
+export type WebhookDeliveryStatus = { 'Failed' : null } |
+  { 'Delivered' : null } |
+  { 'Pending' : null };
+export interface WebhookUpdate {
+  'url' : [] | [string],
+  'enabled' : [] | [boolean],
+  'event_filter' : [] | [Array<ActivityAction>],
+}
 export interface _SERVICE {
   'accept_invite' : ActorMethod<[string], Result>,
+  'archive_project' : ActorMethod<[string], Result>,
   'cancel_invite' : ActorMethod<[string], Result>,
+  'clone_project' : ActorMethod<
+    [string, string, Owner, CloneProjectOptions],
+    Result_1
+  >,
+  'create_api_token' : ActorMethod<[string], Result_1>,
   'create_project' : ActorMethod<[string, string, Owner], Result_1>,
   'create_team' : ActorMethod<[string, string, boolean], Result_1>,
   'create_user' : ActorMethod<[UserProfile], Result_2>,
   'decline_invite' : ActorMethod<[string], Result>,
   'delete_project' : ActorMethod<[string], Result>,
   'delete_team' : ActorMethod<[string], Result>,
+  'delete_webhook' : ActorMethod<[string], Result>,
+  'export_project' : ActorMethod<[string, bigint], Result_3>,
+  'get_activity_retention' : ActorMethod<[], ActivityRetention>,
+  'get_api_tokens' : ActorMethod<[], Array<ApiToken>>,
   'get_invites' : ActorMethod<[Principal], Array<Invite>>,
+  'get_notifications' : ActorMethod<[bigint, bigint], NotificationPage>,
   'get_pending_invites' : ActorMethod<[Principal], Array<Invite>>,
   'get_project' : ActorMethod<[string], [] | [Project]>,
+  'get_project_activity' : ActorMethod<[string, bigint, bigint], Result_4>,
+  'get_project_webhooks' : ActorMethod<[string], Result_5>,
   'get_public_teams' : ActorMethod<[], Array<Team>>,
   'get_team' : ActorMethod<[string], [] | [Team]>,
-  'get_team_projects' : ActorMethod<[string], Array<Project>>,
+  'get_team_activity' : ActorMethod<[string, bigint, bigint], Result_4>,
+  'get_team_project_templates' : ActorMethod<[string], Array<Project>>,
+  'get_team_projects' : ActorMethod<[string, [] | [boolean]], Array<Project>>,
+  'get_unread_notification_count' : ActorMethod<[], bigint>,
   'get_user' : ActorMethod<[Principal], [] | [User]>,
-  'get_user_projects' : ActorMethod<[Principal], Array<Project>>,
+  'get_user_activity' : ActorMethod<[Principal, bigint, bigint], Result_4>,
+  'get_user_projects' : ActorMethod<
+    [Principal, [] | [boolean]],
+    Array<Project>
+  >,
   'get_user_teams' : ActorMethod<[Principal], Array<Team>>,
   'get_users' : ActorMethod<[], Array<User>>,
+  'get_webhook_deliveries' : ActorMethod<[string], Result_6>,
   'health_check' : ActorMethod<[], string>,
+  'http_request' : ActorMethod<[HttpRequest], HttpResponse>,
+  'http_request_update' : ActorMethod<[HttpRequest], HttpResponse>,
   'invite_user' : ActorMethod<[InviteTarget, Role, Principal], Result_1>,
+  'mark_all_notifications_read' : ActorMethod<[], Result>,
+  'mark_notification_read' : ActorMethod<[bigint], Result>,
+  'register_webhook' : ActorMethod<
+    [string, string, Array<ActivityAction>],
+    Result_1
+  >,
   'remove_member' : ActorMethod<[InviteTarget, Principal], Result>,
+  'restore_project' : ActorMethod<[string], Result>,
+  'revoke_api_token' : ActorMethod<[string], Result>,
+  'set_activity_retention' : ActorMethod<[ActivityRetention], Result>,
   'transfer_ownership' : ActorMethod<[string, Owner], Result>,
+  'update_notification_preferences' : ActorMethod<
+    [NotificationPreferences],
+    Result
+  >,
   'update_profile' : ActorMethod<[Principal, UserProfileUpdate], Result>,
   'update_project' : ActorMethod<[string, ProjectUpdate], Result>,
   'update_team' : ActorMethod<[string, TeamUpdate], Result>,
   'update_theme_preferences' : ActorMethod<[ThemePreferences], Result>,
   'update_username' : ActorMethod<[Principal, string], Result>,
+  'update_webhook' : ActorMethod<[string, WebhookUpdate], Result>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
 export declare const init: (args: { IDL: typeof IDL }) => IDL.Type[];
@@ -1,6 +1,7 @@
 export const idlFactory = ({ IDL }) => {
   const Error = IDL.Variant({
     'InvalidInput' : IDL.Text,
+    'ProjectArchived' : IDL.Null,
     'NotFound' : IDL.Null,
     'InviteNotFound' : IDL.Null,
     'InviteExpired' : IDL.Null,
@@ -14,13 +15,40 @@ export const idlFactory = ({ IDL }) => {
   });
   const Result = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : Error });
   const Owner = IDL.Variant({ 'Team' : IDL.Text, 'User' : IDL.Principal });
+  const CloneProjectOptions = IDL.Record({ 'include_members' : IDL.Bool });
   const Result_1 = IDL.Variant({ 'Ok' : IDL.Text, 'Err' : Error });
+  const NotificationKind = IDL.Variant({
+    'DailyDigest' : IDL.Null,
+    'InviteAccepted' : IDL.Null,
+    'InviteDeclined' : IDL.Null,
+    'InviteReceived' : IDL.Null,
+    'MemberRemoved' : IDL.Null,
+  });
+  const NotificationDelivery = IDL.Variant({
+    'Off' : IDL.Null,
+    'Immediate' : IDL.Null,
+    'Digest' : IDL.Null,
+  });
+  const NotificationPreference = IDL.Record({
+    'kind' : NotificationKind,
+    'delivery' : NotificationDelivery,
+  });
+  const QuietHours = IDL.Record({
+    'start_minute' : IDL.Nat16,
+    'end_minute' : IDL.Nat16,
+  });
+  const NotificationPreferences = IDL.Record({
+    'deliveries' : IDL.Vec(NotificationPreference),
+    'utc_offset_minutes' : IDL.Int16,
+    'quiet_hours' : IDL.Opt(QuietHours),
+  });
   const ThemePreferences = IDL.Record({
     'color' : IDL.Text,
     'dark_mode' : IDL.Bool,
   });
   const UserProfile = IDL.Record({
     'bio' : IDL.Opt(IDL.Text),
+    'notification_preferences' : IDL.Opt(NotificationPreferences),
     'username' : IDL.Text,
     'avatar_url' : IDL.Opt(IDL.Text),
     'name' : IDL.Text,
@@ -28,6 +56,23 @@ export const idlFactory = ({ IDL }) => {
     'email' : IDL.Opt(IDL.Text),
   });
   const Result_2 = IDL.Variant({ 'Ok' : IDL.Principal, 'Err' : Error });
+  const ExportChunk = IDL.Record({
+    'snapshot' : IDL.Nat64,
+    'data' : IDL.Vec(IDL.Nat8),
+    'offset' : IDL.Nat64,
+    'total_size' : IDL.Nat64,
+  });
+  const Result_3 = IDL.Variant({ 'Ok' : ExportChunk, 'Err' : Error });
+  const ActivityRetention = IDL.Record({
+    'max_entries_per_scope' : IDL.Nat64,
+    'max_total_entries' : IDL.Nat64,
+  });
+  const ApiToken = IDL.Record({
+    'id' : IDL.Text,
+    'owner' : IDL.Principal,
+    'name' : IDL.Text,
+    'created_at' : IDL.Nat64,
+  });
   const InviteStatus = IDL.Variant({
     'Accepted' : IDL.Null,
     'Declined' : IDL.Null,
@@ -49,6 +94,31 @@ export const idlFactory = ({ IDL }) => {
     'invited_by' : IDL.Principal,
     'invited_user' : IDL.Principal,
   });
+  const DigestItem = IDL.Record({
+    'actor' : IDL.Principal,
+    'kind' : NotificationKind,
+    'invite_id' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'target' : InviteTarget,
+  });
+  const Notification = IDL.Record({
+    'id' : IDL.Nat64,
+    'actor' : IDL.Principal,
+    'kind' : NotificationKind,
+    'read' : IDL.Bool,
+    'invite_id' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'target' : IDL.Opt(InviteTarget),
+    'digest' : IDL.Vec(DigestItem),
+  });
+  const NotificationPage = IDL.Record({
+    'total' : IDL.Nat64,
+    'notifications' : IDL.Vec(Notification),
+  });
+  const ProjectStatus = IDL.Variant({
+    'Active' : IDL.Null,
+    'Archived' : IDL.Null,
+  });
   const TeamMember = IDL.Record({
     'principal' : IDL.Principal,
     'role' : Role,
@@ -56,13 +126,69 @@ export const idlFactory = ({ IDL }) => {
   });
   const Project = IDL.Record({
     'id' : IDL.Text,
+    'status' : ProjectStatus,
     'updated_at' : IDL.Nat64,
     'members' : IDL.Vec(TeamMember),
+    'is_template' : IDL.Bool,
     'owner' : Owner,
     'name' : IDL.Text,
     'description' : IDL.Text,
     'created_at' : IDL.Nat64,
   });
+  const ActivityEntity = IDL.Variant({
+    'Webhook' : IDL.Text,
+    'Team' : IDL.Text,
+    'User' : IDL.Principal,
+    'Project' : IDL.Text,
+    'Invite' : IDL.Text,
+  });
+  const ActivityAction = IDL.Variant({
+    'MemberAdded' : IDL.Null,
+    'InviteCancelled' : IDL.Null,
+    'Updated' : IDL.Null,
+    'InviteAccepted' : IDL.Null,
+    'InviteDeclined' : IDL.Null,
+    'InviteSent' : IDL.Null,
+    'Archived' : IDL.Null,
+    'Restored' : IDL.Null,
+    'MemberRemoved' : IDL.Null,
+    'Created' : IDL.Null,
+    'Deleted' : IDL.Null,
+    'OwnershipTransferred' : IDL.Null,
+  });
+  const FieldChange = IDL.Record({
+    'field' : IDL.Text,
+    'after' : IDL.Opt(IDL.Text),
+    'before' : IDL.Opt(IDL.Text),
+  });
+  const ActivityEntry = IDL.Record({
+    'id' : IDL.Nat64,
+    'entity' : ActivityEntity,
+    'action' : ActivityAction,
+    'actor' : IDL.Principal,
+    'team_id' : IDL.Opt(IDL.Text),
+    'timestamp' : IDL.Nat64,
+    'project_id' : IDL.Opt(IDL.Text),
+    'changes' : IDL.Vec(FieldChange),
+  });
+  const ActivityPage = IDL.Record({
+    'total' : IDL.Nat64,
+    'entries' : IDL.Vec(ActivityEntry),
+  });
+  const Result_4 = IDL.Variant({ 'Ok' : ActivityPage, 'Err' : Error });
+  const Webhook = IDL.Record({
+    'id' : IDL.Text,
+    'url' : IDL.Text,
+    'updated_at' : IDL.Nat64,
+    'secret' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'enabled' : IDL.Bool,
+    'event_filter' : IDL.Vec(ActivityAction),
+    'project_id' : IDL.Text,
+    'consecutive_failures' : IDL.Nat32,
+  });
+  const Result_5 = IDL.Variant({ 'Ok' : IDL.Vec(Webhook), 'Err' : Error });
   const Team = IDL.Record({
     'id' : IDL.Text,
     'is_public' : IDL.Bool,
@@ -79,8 +205,42 @@ export const idlFactory = ({ IDL }) => {
     'created_at' : IDL.Nat64,
     'profile' : UserProfile,
   });
+  const WebhookDeliveryStatus = IDL.Variant({
+    'Failed' : IDL.Null,
+    'Delivered' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const WebhookDelivery = IDL.Record({
+    'id' : IDL.Nat64,
+    'last_error' : IDL.Opt(IDL.Text),
+    'status' : WebhookDeliveryStatus,
+    'updated_at' : IDL.Nat64,
+    'attempts' : IDL.Nat32,
+    'created_at' : IDL.Nat64,
+    'activity_id' : IDL.Nat64,
+    'last_status_code' : IDL.Opt(IDL.Nat16),
+    'payload' : IDL.Text,
+    'webhook_id' : IDL.Text,
+  });
+  const Result_6 = IDL.Variant({
+    'Ok' : IDL.Vec(WebhookDelivery),
+    'Err' : Error,
+  });
+  const HttpRequest = IDL.Record({
+    'url' : IDL.Text,
+    'method' : IDL.Text,
+    'body' : IDL.Vec(IDL.Nat8),
+    'headers' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Text)),
+  });
+  const HttpResponse = IDL.Record({
+    'body' : IDL.Vec(IDL.Nat8),
+    'headers' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Text)),
+    'upgrade' : IDL.Opt(IDL.Bool),
+    'status_code' : IDL.Nat16,
+  });
   const UserProfileUpdate = IDL.Record({
     'bio' : IDL.Opt(IDL.Opt(IDL.Text)),
+    'notification_preferences' : IDL.Opt(IDL.Opt(NotificationPreferences)),
     'username' : IDL.Opt(IDL.Text),
     'avatar_url' : IDL.Opt(IDL.Opt(IDL.Text)),
     'name' : IDL.Opt(IDL.Text),
@@ -88,6 +248,7 @@ export const idlFactory = ({ IDL }) => {
     'email' : IDL.Opt(IDL.Opt(IDL.Text)),
   });
   const ProjectUpdate = IDL.Record({
+    'is_template' : IDL.Opt(IDL.Bool),
     'name' : IDL.Opt(IDL.Text),
     'description' : IDL.Opt(IDL.Text),
   });
@@ -96,41 +257,106 @@ export const idlFactory = ({ IDL }) => {
     'name' : IDL.Opt(IDL.Text),
     'description' : IDL.Opt(IDL.Text),
   });
+  const WebhookUpdate = IDL.Record({
+    'url' : IDL.Opt(IDL.Text),
+    'enabled' : IDL.Opt(IDL.Bool),
+    'event_filter' : IDL.Opt(IDL.Vec(ActivityAction)),
+  });
   return IDL.Service({
     'accept_invite' : IDL.Func([IDL.Text], [Result], []),
+    'archive_project' : IDL.Func([IDL.Text], [Result], []),
     'cancel_invite' : IDL.Func([IDL.Text], [Result], []),
+    'clone_project' : IDL.Func(
+        [IDL.Text, IDL.Text, Owner, CloneProjectOptions],
+        [Result_1],
+        [],
+      ),
+    'create_api_token' : IDL.Func([IDL.Text], [Result_1], []),
     'create_project' : IDL.Func([IDL.Text, IDL.Text, Owner], [Result_1], []),
     'create_team' : IDL.Func([IDL.Text, IDL.Text, IDL.Bool], [Result_1], []),
     'create_user' : IDL.Func([UserProfile], [Result_2], []),
     'decline_invite' : IDL.Func([IDL.Text], [Result], []),
     'delete_project' : IDL.Func([IDL.Text], [Result], []),
     'delete_team' : IDL.Func([IDL.Text], [Result], []),
+    'delete_webhook' : IDL.Func([IDL.Text], [Result], []),
+    'export_project' : IDL.Func([IDL.Text, IDL.Nat64], [Result_3], ['query']),
+    'get_activity_retention' : IDL.Func([], [ActivityRetention], ['query']),
+    'get_api_tokens' : IDL.Func([], [IDL.Vec(ApiToken)], ['query']),
     'get_invites' : IDL.Func([IDL.Principal], [IDL.Vec(Invite)], ['query']),
+    'get_notifications' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64],
+        [NotificationPage],
+        ['query'],
+      ),
     'get_pending_invites' : IDL.Func(
         [IDL.Principal],
         [IDL.Vec(Invite)],
         ['query'],
       ),
     'get_project' : IDL.Func([IDL.Text], [IDL.Opt(Project)], ['query']),
+    'get_project_activity' : IDL.Func(
+        [IDL.Text, IDL.Nat64, IDL.Nat64],
+        [Result_4],
+        ['query'],
+      ),
+    'get_project_webhooks' : IDL.Func([IDL.Text], [Result_5], ['query']),
     'get_public_teams' : IDL.Func([], [IDL.Vec(Team)], ['query']),
     'get_team' : IDL.Func([IDL.Text], [IDL.Opt(Team)], ['query']),
-    'get_team_projects' : IDL.Func([IDL.Text], [IDL.Vec(Project)], ['query']),
+    'get_team_activity' : IDL.Func(
+        [IDL.Text, IDL.Nat64, IDL.Nat64],
+        [Result_4],
+        ['query'],
+      ),
+    'get_team_project_templates' : IDL.Func(
+        [IDL.Text],
+        [IDL.Vec(Project)],
+        ['query'],
+      ),
+    'get_team_projects' : IDL.Func(
+        [IDL.Text, IDL.Opt(IDL.Bool)],
+        [IDL.Vec(Project)],
+        ['query'],
+      ),
+    'get_unread_notification_count' : IDL.Func([], [IDL.Nat64], ['query']),
     'get_user' : IDL.Func([IDL.Principal], [IDL.Opt(User)], ['query']),
+    'get_user_activity' : IDL.Func(
+        [IDL.Principal, IDL.Nat64, IDL.Nat64],
+        [Result_4],
+        ['query'],
+      ),
     'get_user_projects' : IDL.Func(
-        [IDL.Principal],
+        [IDL.Principal, IDL.Opt(IDL.Bool)],
         [IDL.Vec(Project)],
         ['query'],
       ),
     'get_user_teams' : IDL.Func([IDL.Principal], [IDL.Vec(Team)], ['query']),
     'get_users' : IDL.Func([], [IDL.Vec(User)], ['query']),
+    'get_webhook_deliveries' : IDL.Func([IDL.Text], [Result_6], ['query']),
     'health_check' : IDL.Func([], [IDL.Text], ['query']),
+    'http_request' : IDL.Func([HttpRequest], [HttpResponse], ['query']),
+    'http_request_update' : IDL.Func([HttpRequest], [HttpResponse], []),
     'invite_user' : IDL.Func(
         [InviteTarget, Role, IDL.Principal],
         [Result_1],
         [],
       ),
+    'mark_all_notifications_read' : IDL.Func([], [Result], []),
+    'mark_notification_read' : IDL.Func([IDL.Nat64], [Result], []),
+    'register_webhook' : IDL.Func(
+        [IDL.Text, IDL.Text, IDL.Vec(ActivityAction)],
+        [Result_1],
+        [],
+      ),
     'remove_member' : IDL.Func([InviteTarget, IDL.Principal], [Result], []),
+    'restore_project' : IDL.Func([IDL.Text], [Result], []),
+    'revoke_api_token' : IDL.Func([IDL.Text], [Result], []),
+    'set_activity_retention' : IDL.Func([ActivityRetention], [Result], []),
     'transfer_ownership' : IDL.Func([IDL.Text, Owner], [Result], []),
+    'update_notification_preferences' : IDL.Func(
+        [NotificationPreferences],
+        [Result],
+        [],
+      ),
     'update_profile' : IDL.Func(
         [IDL.Principal, UserProfileUpdate],
         [Result],
@@ -140,6 +366,7 @@ export const idlFactory = ({ IDL }) => {
     'update_team' : IDL.Func([IDL.Text, TeamUpdate], [Result], []),
     'update_theme_preferences' : IDL.Func([ThemePreferences], [Result], []),
     'update_username' : IDL.Func([IDL.Principal, IDL.Text], [Result], []),
+    'update_webhook' : IDL.Func([IDL.Text, WebhookUpdate], [Result], []),
   });
 };
 export const init = ({ IDL }) => { return []; };
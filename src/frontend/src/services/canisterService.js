@@ -130,14 +130,14 @@ class CanisterService {
     return await backendActor.transfer_ownership(projectId, newOwner);
   }
 
-  async getUserProjects(userId) {
+  async getUserProjects(userId, includeArchived = false) {
     const backendActor = await createAuthenticatedBackendActor();
-    return await backendActor.get_user_projects(userId);
+    return await backendActor.get_user_projects(userId, [includeArchived]);
   }
 
-  async getTeamProjects(teamId) {
+  async getTeamProjects(teamId, includeArchived = false) {
     const backendActor = await createAuthenticatedBackendActor();
-    return await backendActor.get_team_projects(teamId);
+    return await backendActor.get_team_projects(teamId, [includeArchived]);
   }
 
   // Invitation management